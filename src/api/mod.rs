@@ -1,5 +1,29 @@
-mod rest;
-mod websocket;
-
-pub use rest::{create_router, ApiState};
-pub use websocket::{ws_handler, WsState, VerificationEvent};
\ No newline at end of file
+mod rest;
+mod websocket;
+
+pub use rest::{create_router, ApiState};
+pub use websocket::{ws_handler, EventKind, WsRequest, WsState, VerificationEvent};
+
+use std::time::Duration;
+
+/// Timeout for a wait-for-finality request, capped at the configured maximum
+fn finality_wait_timeout(requested_ms: Option<u64>, max: Duration) -> Duration {
+    requested_ms
+        .map(Duration::from_millis)
+        .unwrap_or(max)
+        .min(max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finality_wait_timeout_is_capped() {
+        let max = Duration::from_secs(120);
+
+        assert_eq!(finality_wait_timeout(None, max), max);
+        assert_eq!(finality_wait_timeout(Some(5_000), max), Duration::from_secs(5));
+        assert_eq!(finality_wait_timeout(Some(600_000), max), max);
+    }
+}
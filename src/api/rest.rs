@@ -1,117 +1,377 @@
-use axum::{
-    extract::{Path, State},
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
-};
-use serde::{Deserialize, Serialize};
-use solana_sdk::signature::Signature;
-use std::sync::Arc;
-use tracing::info;
-
-use crate::error::StauroXError;
-use crate::types::VerificationResult;
-use crate::verification::VerificationEngine;
-
-/// API State
-#[derive(Clone)]
-pub struct ApiState {
-    pub engine: Arc<VerificationEngine>,
-}
-
-/// Request body for verification
-#[derive(Debug, Deserialize)]
-pub struct VerifyRequest {
-    pub signature: String,
-}
-
-/// Health check response
-#[derive(Debug, Serialize)]
-pub struct HealthResponse {
-    pub status: String,
-    pub network: String,
-}
-
-/// Create REST API router
-pub fn create_router(state: ApiState) -> Router {
-    Router::new()
-        .route("/health", get(health_check))
-        .route("/verify", post(verify_transaction))
-        .route("/verify/:signature", get(get_verification))
-        .with_state(state)
-}
-
-/// Health check endpoint
-async fn health_check(State(state): State<ApiState>) -> impl IntoResponse {
-    let network_health = state.engine.health_monitor.get_health().await;
-    
-    Json(HealthResponse {
-        status: "ok".to_string(),
-        network: format!("{:?}", network_health),
-    })
-}
-
-/// Verify transaction endpoint
-async fn verify_transaction(
-    State(state): State<ApiState>,
-    Json(payload): Json<VerifyRequest>,
-) -> Result<Json<VerificationResult>, AppError> {
-    info!("API: Verifying transaction {}", payload.signature);
-
-    let signature = payload
-        .signature
-        .parse::<Signature>()
-        .map_err(|_| AppError::InvalidSignature)?;
-
-    let result = state
-        .engine
-        .verify_transaction(&signature)
-        .await
-        .map_err(AppError::Verification)?;
-
-    Ok(Json(result))
-}
-
-/// Get verification status endpoint
-async fn get_verification(
-    State(state): State<ApiState>,
-    Path(signature): Path<String>,
-) -> Result<Json<VerificationResult>, AppError> {
-    info!("API: Getting verification for {}", signature);
-
-    let signature = signature
-        .parse::<Signature>()
-        .map_err(|_| AppError::InvalidSignature)?;
-
-    let result = state
-        .engine
-        .verify_transaction(&signature)
-        .await
-        .map_err(AppError::Verification)?;
-
-    Ok(Json(result))
-}
-
-/// API error wrapper
-pub enum AppError {
-    InvalidSignature,
-    Verification(StauroXError),
-}
-
-impl IntoResponse for AppError {
-    fn into_response(self) -> axum::response::Response {
-        let (status, message) = match self {
-            AppError::InvalidSignature => (
-                StatusCode::BAD_REQUEST,
-                "Invalid transaction signature".to_string(),
-            ),
-            AppError::Verification(e) => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Verification error: {}", e),
-            ),
-        };
-
-        (status, Json(serde_json::json!({ "error": message }))).into_response()
-    }
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+use crate::error::StauroXError;
+use crate::storage::StoreQuery;
+use crate::types::{
+    AcceptancePolicy, AuditTrail, FinalityLevel, ForkReport, SourceLag, VerificationResult,
+};
+use crate::verification::{CacheStats, VerificationEngine};
+
+use super::finality_wait_timeout;
+
+/// API State
+#[derive(Clone)]
+pub struct ApiState {
+    pub engine: Arc<VerificationEngine>,
+    pub max_finality_wait: Duration,
+    pub max_batch_size: usize,
+    pub policies: Arc<HashMap<String, AcceptancePolicy>>,
+}
+
+impl ApiState {
+    /// Look up a requested policy by name
+    fn policy(&self, name: Option<&str>) -> Result<Option<(String, AcceptancePolicy)>, AppError> {
+        let Some(name) = name else {
+            return Ok(None);
+        };
+
+        self.policies
+            .get(name)
+            .map(|policy| Some((name.to_string(), policy.clone())))
+            .ok_or_else(|| AppError::UnknownPolicy(name.to_string()))
+    }
+}
+
+/// Request body for verification
+#[derive(Debug, Deserialize)]
+pub struct VerifyRequest {
+    pub signature: String,
+    #[serde(flatten)]
+    pub params: VerifyParams,
+}
+
+/// Optional verification parameters
+///
+/// With `wait_for` set, the request blocks until the transaction reaches
+/// that finality level or `timeout_ms` (capped by config) expires. With
+/// `audit` set, the result includes its per-step audit trail. With
+/// `policy` set, the result reports which rules of that named policy passed.
+/// With `no_cache` set, the pipeline runs even if a cached result exists.
+/// With `stored` set, a persisted verdict is returned as is when there is one.
+#[derive(Debug, Default, Deserialize)]
+pub struct VerifyParams {
+    pub wait_for: Option<FinalityLevel>,
+    pub timeout_ms: Option<u64>,
+    #[serde(default)]
+    pub audit: bool,
+    pub policy: Option<String>,
+    #[serde(default)]
+    pub no_cache: bool,
+    #[serde(default)]
+    pub stored: bool,
+}
+
+/// Request body for batch verification
+#[derive(Debug, Deserialize)]
+pub struct BatchVerifyRequest {
+    pub signatures: Vec<String>,
+    /// Include each result's audit trail
+    #[serde(default)]
+    pub audit: bool,
+    /// Evaluate each result against this named policy
+    pub policy: Option<String>,
+    /// Verify every signature even if cached results exist
+    #[serde(default)]
+    pub no_cache: bool,
+}
+
+/// Outcome for one signature of a batch
+#[derive(Debug, Serialize)]
+pub struct BatchVerifyItem {
+    pub signature: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<VerificationResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Batch verification response, in request order
+#[derive(Debug, Serialize)]
+pub struct BatchVerifyResponse {
+    pub results: Vec<BatchVerifyItem>,
+}
+
+/// Health check response
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: String,
+    pub network: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fork: Option<ForkReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub lagging: Vec<SourceLag>,
+}
+
+/// Create REST API router
+pub fn create_router(state: ApiState) -> Router {
+    Router::new()
+        .route("/health", get(health_check))
+        .route("/verify", post(verify_transaction))
+        .route("/verify/batch", post(verify_batch))
+        .route("/verify/:signature", get(get_verification))
+        .route("/verify/:signature/audit", get(get_audit_trail))
+        .route("/cache/stats", get(cache_stats))
+        .route("/verifications", get(query_verifications))
+        .with_state(state)
+}
+
+/// Health check endpoint
+async fn health_check(State(state): State<ApiState>) -> impl IntoResponse {
+    let network_health = state.engine.health_monitor.get_health().await;
+    let fork = state.engine.health_monitor.get_fork_report().await;
+    let lagging = state.engine.health_monitor.get_lagging_sources().await;
+    
+    Json(HealthResponse {
+        status: "ok".to_string(),
+        network: format!("{:?}", network_health),
+        fork,
+        lagging,
+    })
+}
+
+/// Verify transaction endpoint
+async fn verify_transaction(
+    State(state): State<ApiState>,
+    Json(payload): Json<VerifyRequest>,
+) -> Result<Json<VerificationResult>, AppError> {
+    info!("API: Verifying transaction {}", payload.signature);
+
+    let signature = payload
+        .signature
+        .parse::<Signature>()
+        .map_err(|_| AppError::InvalidSignature)?;
+
+    verify_with_params(&state, &signature, payload.params).await
+}
+
+/// Batch verification endpoint
+///
+/// Malformed signatures are reported per item instead of failing the batch.
+async fn verify_batch(
+    State(state): State<ApiState>,
+    Json(payload): Json<BatchVerifyRequest>,
+) -> Result<Json<BatchVerifyResponse>, AppError> {
+    info!("API: Verifying batch of {} transactions", payload.signatures.len());
+
+    if payload.signatures.len() > state.max_batch_size {
+        return Err(AppError::BatchTooLarge(state.max_batch_size));
+    }
+
+    let policy = state.policy(payload.policy.as_deref())?;
+
+    let parsed: Vec<Option<Signature>> = payload
+        .signatures
+        .iter()
+        .map(|signature| signature.parse::<Signature>().ok())
+        .collect();
+    let valid: Vec<Signature> = parsed.iter().flatten().copied().collect();
+
+    let verified = if payload.no_cache {
+        state.engine.verify_batch_fresh(&valid).await
+    } else {
+        state.engine.verify_batch(&valid).await
+    };
+    let mut verified = verified.into_iter();
+
+    let audit = payload.audit;
+    let results = payload
+        .signatures
+        .into_iter()
+        .zip(parsed)
+        .map(|(signature, parsed)| {
+            let outcome = match parsed {
+                Some(_) => verified.next().unwrap_or_else(|| {
+                    Err(StauroXError::verification("Missing batch result"))
+                }),
+                None => {
+                    return BatchVerifyItem {
+                        signature,
+                        result: None,
+                        error: Some("Invalid transaction signature".to_string()),
+                    }
+                }
+            };
+
+            match outcome {
+                Ok(mut result) => {
+                    if !audit {
+                        result.audit = None;
+                    }
+                    if let Some((name, policy)) = &policy {
+                        result = result.with_policy(name, policy);
+                    }
+                    BatchVerifyItem {
+                        signature,
+                        result: Some(result),
+                        error: None,
+                    }
+                }
+                Err(e) => BatchVerifyItem {
+                    signature,
+                    result: None,
+                    error: Some(e.to_string()),
+                },
+            }
+        })
+        .collect();
+
+    Ok(Json(BatchVerifyResponse { results }))
+}
+
+/// Get verification status endpoint
+async fn get_verification(
+    State(state): State<ApiState>,
+    Path(signature): Path<String>,
+    Query(params): Query<VerifyParams>,
+) -> Result<Json<VerificationResult>, AppError> {
+    info!("API: Getting verification for {}", signature);
+
+    let signature = signature
+        .parse::<Signature>()
+        .map_err(|_| AppError::InvalidSignature)?;
+
+    verify_with_params(&state, &signature, params).await
+}
+
+/// Run a one-shot verification, or wait for finality if requested
+async fn verify_with_params(
+    state: &ApiState,
+    signature: &Signature,
+    params: VerifyParams,
+) -> Result<Json<VerificationResult>, AppError> {
+    let policy = state.policy(params.policy.as_deref())?;
+
+    let stored = match (params.stored, state.engine.store()) {
+        (true, Some(store)) => store.get(*signature).await.map_err(AppError::Verification)?,
+        _ => None,
+    };
+
+    let mut result = match stored {
+        Some(result) => Ok(result),
+        None => verify_or_wait(state, signature, &params).await,
+    }
+    .map_err(AppError::Verification)?;
+
+    if !params.audit {
+        result.audit = None;
+    }
+    if let Some((name, policy)) = policy {
+        result = result.with_policy(&name, &policy);
+    }
+
+    Ok(Json(result))
+}
+
+async fn verify_or_wait(
+    state: &ApiState,
+    signature: &Signature,
+    params: &VerifyParams,
+) -> crate::error::Result<VerificationResult> {
+    match params.wait_for {
+        Some(target) => {
+            let timeout = finality_wait_timeout(params.timeout_ms, state.max_finality_wait);
+            state
+                .engine
+                .verify_until_finality(signature, target, timeout)
+                .await
+        }
+        None if params.no_cache => state.engine.verify_fresh(signature).await,
+        None => state.engine.verify_transaction(signature).await,
+    }
+}
+
+/// Audit trail of the latest verification of a signature
+async fn get_audit_trail(
+    State(state): State<ApiState>,
+    Path(signature): Path<String>,
+) -> Result<Json<AuditTrail>, AppError> {
+    let signature = signature
+        .parse::<Signature>()
+        .map_err(|_| AppError::InvalidSignature)?;
+
+    state
+        .engine
+        .audit_trail(&signature)
+        .await
+        .map(Json)
+        .ok_or(AppError::NotFound)
+}
+
+/// Result cache hit/miss counters (all zero without a cache)
+async fn cache_stats(State(state): State<ApiState>) -> Json<CacheStats> {
+    Json(state.engine.cache_stats().await.unwrap_or_default())
+}
+
+/// Stored verifications matching the query, newest slot first
+async fn query_verifications(
+    State(state): State<ApiState>,
+    Query(query): Query<StoreQuery>,
+) -> Result<Json<Vec<VerificationResult>>, AppError> {
+    let store = state.engine.store().ok_or(AppError::StorageDisabled)?;
+
+    let mut results = store.query(query).await.map_err(AppError::Verification)?;
+    for result in &mut results {
+        result.audit = None;
+    }
+
+    Ok(Json(results))
+}
+
+/// API error wrapper
+pub enum AppError {
+    InvalidSignature,
+    NotFound,
+    BatchTooLarge(usize),
+    UnknownPolicy(String),
+    StorageDisabled,
+    Verification(StauroXError),
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> axum::response::Response {
+        let (status, message) = match self {
+            AppError::InvalidSignature => (
+                StatusCode::BAD_REQUEST,
+                "Invalid transaction signature".to_string(),
+            ),
+            AppError::NotFound => (
+                StatusCode::NOT_FOUND,
+                "No audit trail recorded for this signature".to_string(),
+            ),
+            AppError::BatchTooLarge(max) => (
+                StatusCode::PAYLOAD_TOO_LARGE,
+                format!("Batch exceeds {} signatures", max),
+            ),
+            AppError::UnknownPolicy(name) => (
+                StatusCode::BAD_REQUEST,
+                format!("Unknown acceptance policy: {}", name),
+            ),
+            AppError::StorageDisabled => (
+                StatusCode::NOT_IMPLEMENTED,
+                "Verification storage is not configured".to_string(),
+            ),
+            AppError::Verification(e @ StauroXError::FinalityTimeout { .. }) => (
+                StatusCode::REQUEST_TIMEOUT,
+                e.to_string(),
+            ),
+            AppError::Verification(e) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Verification error: {}", e),
+            ),
+        };
+
+        (status, Json(serde_json::json!({ "error": message }))).into_response()
+    }
 }
\ No newline at end of file
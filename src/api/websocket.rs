@@ -1,95 +1,291 @@
-use axum::{
-    extract::{
-        ws::{Message, WebSocket, WebSocketUpgrade},
-        State,
-    },
-    response::IntoResponse,
-};
-use futures::{SinkExt, StreamExt};
-use serde::Serialize;
-use tokio::sync::broadcast;
-use tracing::{debug, info};
-
-use crate::types::VerificationResult;
-
-/// WebSocket state
-#[derive(Clone)]
-pub struct WsState {
-    pub tx: broadcast::Sender<VerificationEvent>,
-}
-
-impl WsState {
-    pub fn new() -> Self {
-        let (tx, _) = broadcast::channel(100);
-        Self { tx }
-    }
-
-        pub fn broadcast(&self, event: VerificationEvent) {
-                let _ = self.tx.send(event);
-    }
-}
-
-/// Verification event for WebSocket
-#[derive(Debug, Clone, Serialize)]
-pub struct VerificationEvent {
-    pub signature: String,
-    pub verified: bool,
-    pub slot: u64,
-    pub risk_score: f64,
-    pub timestamp: String,
-}
-
-impl From<VerificationResult> for VerificationEvent {
-    fn from(result: VerificationResult) -> Self {
-        Self {
-            signature: result.signature.to_string(),
-            verified: result.verified,
-            slot: result.slot,
-            risk_score: result.risk_score,
-            timestamp: result.timestamp.to_rfc3339(),
-        }
-    }
-}
-
-/// WebSocket upgrade handler
-pub async fn ws_handler(
-    ws: WebSocketUpgrade,
-    State(state): State<WsState>,
-) -> impl IntoResponse {
-    ws.on_upgrade(|socket| handle_socket(socket, state))
-}
-
-/// Handle WebSocket connection
-async fn handle_socket(socket: WebSocket, state: WsState) {
-    let (mut sender, mut receiver) = socket.split();
-    let mut rx = state.tx.subscribe();
-
-    info!("New WebSocket connection");
-
-    // Spawn task to send events to client
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(event) = rx.recv().await {
-            let json = serde_json::to_string(&event).unwrap();
-            if sender.send(Message::Text(json)).await.is_err() {
-                break;
-            }
-        }
-    });
-
-    // Handle incoming messages (ping/pong)
-    let mut recv_task = tokio::spawn(async move {
-        while let Some(Ok(msg)) = receiver.next().await {
-            if let Message::Close(_) = msg {
-                break;
-            }
-        }
-    });
-
-    // Wait for either task to finish
-    tokio::select! {
-        _ = &mut send_task => recv_task.abort(),
-        _ = &mut recv_task => send_task.abort(),
-    }
-
-    debug!("WebSocket connection closed");
-}
\ No newline at end of file
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::IntoResponse,
+};
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio::task::JoinSet;
+use tracing::{debug, info};
+
+use crate::types::{
+    Commitment, FinalityLevel, Revocation, VerificationOutcome, VerificationResult,
+};
+use crate::verification::VerificationEngine;
+
+use super::finality_wait_timeout;
+
+// Cap used until `with_engine` supplies the configured one
+const DEFAULT_MAX_FINALITY_WAIT: Duration = Duration::from_secs(120);
+
+// Replies queued for a slow client before request tasks wait on it
+const REPLY_BUFFER: usize = 32;
+
+// Finality waits one connection may have in flight; further requests are rejected
+const MAX_PENDING_WAITS: usize = 8;
+
+/// WebSocket state
+#[derive(Clone)]
+pub struct WsState {
+    pub tx: broadcast::Sender<VerificationEvent>,
+    engine: Option<Arc<VerificationEngine>>,
+    max_finality_wait: Duration,
+}
+
+impl WsState {
+    pub fn new() -> Self {
+        let (tx, _) = broadcast::channel(100);
+        Self {
+            tx,
+            engine: None,
+            max_finality_wait: DEFAULT_MAX_FINALITY_WAIT,
+        }
+    }
+
+    /// Serve wait-for-finality requests from connected clients
+    pub fn with_engine(mut self, engine: Arc<VerificationEngine>, max_finality_wait: Duration) -> Self {
+        self.engine = Some(engine);
+        self.max_finality_wait = max_finality_wait;
+        self
+    }
+
+    pub fn broadcast(&self, event: VerificationEvent) {
+        let _ = self.tx.send(event);
+    }
+}
+
+impl Default for WsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Kind of WebSocket verification event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Verification,
+    /// A previously verified transaction was rolled back
+    Revocation,
+}
+
+/// Verification event for WebSocket
+#[derive(Debug, Clone, Serialize)]
+pub struct VerificationEvent {
+    pub kind: EventKind,
+    pub signature: String,
+    pub verified: bool,
+    pub outcome: VerificationOutcome,
+    pub slot: u64,
+    pub risk_score: f64,
+    pub finality_level: FinalityLevel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation: Option<Revocation>,
+    pub timestamp: String,
+}
+
+impl From<VerificationResult> for VerificationEvent {
+    fn from(result: VerificationResult) -> Self {
+        let kind = match result.revocation {
+            Some(_) => EventKind::Revocation,
+            None => EventKind::Verification,
+        };
+
+        Self {
+            kind,
+            signature: result.signature.to_string(),
+            verified: result.verified,
+            outcome: result.outcome,
+            slot: result.slot,
+            risk_score: result.risk_score,
+            finality_level: result.finality_level,
+            commitment: result.commitment,
+            revocation: result.revocation,
+            timestamp: result.timestamp.to_rfc3339(),
+        }
+    }
+}
+
+/// Message sent by a client
+///
+/// `{"type": "wait_for_finality", "signature": "...", "target": "UltraSafe"}`
+/// replies with a verification event once the target is reached, or with an
+/// error message on failure or timeout.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WsRequest {
+    WaitForFinality {
+        signature: String,
+        target: FinalityLevel,
+        timeout_ms: Option<u64>,
+    },
+}
+
+/// Error reply to a client request
+#[derive(Debug, Serialize)]
+struct WsError {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+    error: String,
+}
+
+/// WebSocket upgrade handler
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<WsState>,
+) -> impl IntoResponse {
+    ws.on_upgrade(|socket| handle_socket(socket, state))
+}
+
+/// Handle WebSocket connection
+async fn handle_socket(socket: WebSocket, state: WsState) {
+    let (mut sender, mut receiver) = socket.split();
+    let mut rx = state.tx.subscribe();
+    let (reply_tx, mut reply_rx) = mpsc::channel::<String>(REPLY_BUFFER);
+
+    info!("New WebSocket connection");
+
+    // Spawn task to send broadcast events and request replies to client
+    let mut send_task = tokio::spawn(async move {
+        loop {
+            let json = tokio::select! {
+                event = rx.recv() => match event {
+                    Ok(event) => serde_json::to_string(&event).unwrap(),
+                    Err(_) => break,
+                },
+                Some(reply) = reply_rx.recv() => reply,
+            };
+            if sender.send(Message::Text(json)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // Handle incoming messages (requests, ping/pong)
+    //
+    // Request tasks live in this task's JoinSet, so they are aborted along
+    // with it when the connection closes.
+    let mut recv_task = tokio::spawn(async move {
+        let mut requests = JoinSet::new();
+        let waits = Arc::new(Semaphore::new(MAX_PENDING_WAITS));
+        while let Some(Ok(msg)) = receiver.next().await {
+            match msg {
+                Message::Text(text) => {
+                    handle_request(&state, &text, &reply_tx, &mut requests, &waits).await
+                }
+                Message::Close(_) => break,
+                _ => {}
+            }
+            while requests.try_join_next().is_some() {}
+        }
+    });
+
+    // Wait for either task to finish
+    tokio::select! {
+        _ = &mut send_task => recv_task.abort(),
+        _ = &mut recv_task => send_task.abort(),
+    }
+
+    debug!("WebSocket connection closed");
+}
+
+/// Run a client request on the connection's task set, replying through `reply_tx`
+///
+/// Each wait holds one of the connection's `waits` permits until it replies.
+async fn handle_request(
+    state: &WsState,
+    text: &str,
+    reply_tx: &mpsc::Sender<String>,
+    requests: &mut JoinSet<()>,
+    waits: &Arc<Semaphore>,
+) {
+    let request = match serde_json::from_str::<WsRequest>(text) {
+        Ok(request) => request,
+        Err(e) => {
+            let _ = reply_tx.send(error_reply(None, format!("Invalid request: {}", e))).await;
+            return;
+        }
+    };
+
+    let WsRequest::WaitForFinality { signature, target, timeout_ms } = request;
+
+    let Some(engine) = state.engine.clone() else {
+        let reply = error_reply(Some(signature), "Verification not available".to_string());
+        let _ = reply_tx.send(reply).await;
+        return;
+    };
+
+    let Ok(parsed) = signature.parse::<Signature>() else {
+        let reply = error_reply(Some(signature), "Invalid transaction signature".to_string());
+        let _ = reply_tx.send(reply).await;
+        return;
+    };
+
+    let Ok(permit) = Arc::clone(waits).try_acquire_owned() else {
+        let error = format!("Too many pending finality waits (max {})", MAX_PENDING_WAITS);
+        let _ = reply_tx.send(error_reply(Some(signature), error)).await;
+        return;
+    };
+
+    let timeout = finality_wait_timeout(timeout_ms, state.max_finality_wait);
+    let reply_tx = reply_tx.clone();
+    requests.spawn(async move {
+        let reply = match engine.verify_until_finality(&parsed, target, timeout).await {
+            Ok(result) => serde_json::to_string(&VerificationEvent::from(result)).unwrap(),
+            Err(e) => error_reply(Some(signature), e.to_string()),
+        };
+        let _ = reply_tx.send(reply).await;
+        drop(permit);
+    });
+}
+
+fn error_reply(signature: Option<String>, error: String) -> String {
+    serde_json::to_string(&WsError { signature, error }).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::monitor::HealthMonitor;
+    use crate::rpc::MultiRpcClient;
+
+    #[tokio::test]
+    async fn test_pending_waits_are_capped_per_connection() {
+        // Unroutable endpoint, so every wait stays in flight
+        let rpc_client = MultiRpcClient::new(
+            vec!["http://10.255.255.1:8899".to_string()],
+            1,
+            Duration::from_secs(5),
+        );
+        let engine = VerificationEngine::new(Arc::new(rpc_client), Arc::new(HealthMonitor::default()));
+        let state = WsState::new().with_engine(Arc::new(engine), Duration::from_secs(60));
+
+        let (reply_tx, mut reply_rx) = mpsc::channel(REPLY_BUFFER);
+        let mut requests = JoinSet::new();
+        let waits = Arc::new(Semaphore::new(MAX_PENDING_WAITS));
+        let request = serde_json::json!({
+            "type": "wait_for_finality",
+            "signature": Signature::new_unique().to_string(),
+            "target": "UltraSafe",
+        })
+        .to_string();
+
+        for _ in 0..MAX_PENDING_WAITS {
+            handle_request(&state, &request, &reply_tx, &mut requests, &waits).await;
+        }
+        assert_eq!(requests.len(), MAX_PENDING_WAITS);
+        assert!(reply_rx.try_recv().is_err());
+
+        handle_request(&state, &request, &reply_tx, &mut requests, &waits).await;
+        assert_eq!(requests.len(), MAX_PENDING_WAITS);
+        assert!(reply_rx.try_recv().unwrap().contains("Too many pending finality waits"));
+    }
+}
@@ -1,221 +1,646 @@
-use serde::{Deserialize, Serialize};
-use std::time::Duration;
-
-use crate::error::{Result, StauroXError};
-
-/// Solana network type
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum Network {
-    Mainnet,
-    Devnet,
-}
-
-impl Network {
-    pub fn default_endpoints(&self) -> Vec<String> {
-        match self {
-            Network::Mainnet => vec![
-                "https://api.mainnet-beta.solana.com".to_string(),
-                "https://solana.publicnode.com".to_string(),
-            ],
-            Network::Devnet => vec![
-                "https://api.devnet.solana.com".to_string(),
-                "https://devnet.sonic.game".to_string(),
-            ],
-        }
-    }
-
-    pub fn name(&self) -> &'static str {
-        match self {
-            Network::Mainnet => "mainnet-beta",
-            Network::Devnet => "devnet",
-        }
-    }
-}
-
-// StauroX configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Config {
-    pub network: Network,
-    pub rpc: RpcConfig,
-    pub monitoring: MonitoringConfig,
-    pub api: ApiConfig,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RpcConfig {
-    pub endpoints: Vec<String>,
-    pub consensus_threshold: usize,
-    pub request_timeout_ms: u64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MonitoringConfig {
-    pub health_check_interval_ms: u64,
-    pub slot_retention_seconds: u64,
-    pub stale_threshold_seconds: i64,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ApiConfig {
-    pub websocket_port: u16,
-    pub rest_port: u16,
-}
-
-impl Config {
-    /// Validate configuration
-    pub fn validate(&self) -> Result<()> {
-        if self.rpc.endpoints.is_empty() {
-            return Err(StauroXError::config("At least one RPC endpoint required"));
-        }
-
-        if self.rpc.consensus_threshold == 0 {
-            return Err(StauroXError::config("Consensus threshold must be > 0"));
-        }
-
-        if self.rpc.consensus_threshold > self.rpc.endpoints.len() {
-            return Err(StauroXError::config(format!(
-                "Consensus threshold ({}) exceeds number of endpoints ({})",
-                self.rpc.consensus_threshold,
-                self.rpc.endpoints.len()
-            )));
-        }
-
-        if self.monitoring.health_check_interval_ms == 0 {
-            return Err(StauroXError::config(
-                "Health check interval must be > 0",
-            ));
-        }
-
-        Ok(())
-    }
-
-    pub fn health_check_interval(&self) -> Duration {
-        Duration::from_millis(self.monitoring.health_check_interval_ms)
-    }
-
-    pub fn request_timeout(&self) -> Duration {
-        Duration::from_millis(self.rpc.request_timeout_ms)
-    }
-}
-
-impl Default for Config {
-    fn default() -> Self {
-        Self::devnet()
-    }
-}
-
-impl Config {
-    /// Create devnet configuration (safer for testing)
-    pub fn devnet() -> Self {
-        let network = Network::Devnet;
-        Self {
-            network,
-            rpc: RpcConfig {
-                endpoints: network.default_endpoints(),
-                consensus_threshold: 3,
-                request_timeout_ms: 5000,
-            },
-            monitoring: MonitoringConfig {
-                health_check_interval_ms: 400,
-                slot_retention_seconds: 30,
-                stale_threshold_seconds: 5,
-            },
-            api: ApiConfig {
-                websocket_port: 8080,
-                rest_port: 8081,
-            },
-        }
-    }
-
-    /// Create mainnet configuration (production)
-    pub fn mainnet() -> Self {
-        let network = Network::Mainnet;
-        Self {
-            network,
-            rpc: RpcConfig {
-                endpoints: network.default_endpoints(),
-                consensus_threshold: 1,
-                request_timeout_ms: 10000,
-            },
-            monitoring: MonitoringConfig {
-                health_check_interval_ms: 2000,
-                slot_retention_seconds: 30,
-                stale_threshold_seconds: 5,
-            },
-            api: ApiConfig {
-                websocket_port: 8080,
-                rest_port: 8081,
-            },
-        }
-    }
-
-    /// Create custom configuration with specific endpoints
-    pub fn custom(network: Network, endpoints: Vec<String>) -> Self {
-        let mut config = match network {
-            Network::Mainnet => Self::mainnet(),
-            Network::Devnet => Self::devnet(),
-        };
-        config.rpc.endpoints = endpoints;
-        config
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_default_config_is_valid() {
-        let config = Config::default();
-        assert!(config.validate().is_ok());
-        assert_eq!(config.network, Network::Devnet);
-    }
-
-    #[test]
-    fn test_devnet_config() {
-        let config = Config::devnet();
-        assert!(config.validate().is_ok());
-        assert_eq!(config.network, Network::Devnet);
-        assert_eq!(config.rpc.endpoints.len(), 4);
-    }
-
-    #[test]
-    fn test_mainnet_config() {
-        let config = Config::mainnet();
-        assert!(config.validate().is_ok());
-        assert_eq!(config.network, Network::Mainnet);
-        assert_eq!(config.rpc.endpoints.len(), 4);
-    }
-
-    #[test]
-    fn test_custom_config() {
-        let custom_endpoints = vec!["https://custom.rpc".to_string()];
-        let config = Config::custom(Network::Devnet, custom_endpoints.clone());
-        assert!(config.validate().is_ok());
-        assert_eq!(config.rpc.endpoints, custom_endpoints);
-    }
-
-    #[test]
-    fn test_network_endpoints() {
-        let devnet_endpoints = Network::Devnet.default_endpoints();
-        let mainnet_endpoints = Network::Mainnet.default_endpoints();
-        
-        assert!(!devnet_endpoints.is_empty());
-        assert!(!mainnet_endpoints.is_empty());
-        assert_ne!(devnet_endpoints, mainnet_endpoints);
-    }
-
-    #[test]
-    fn test_invalid_consensus_threshold() {
-        let mut config = Config::default();
-        config.rpc.consensus_threshold = 10;
-        assert!(config.validate().is_err());
-    }
-
-    #[test]
-    fn test_empty_endpoints() {
-        let mut config = Config::default();
-        config.rpc.endpoints.clear();
-        assert!(config.validate().is_err());
-    }
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::error::{Result, StauroXError};
+use crate::parsers::{BridgeType, ParsedTransaction};
+use crate::types::{AcceptancePolicy, FinalityLevel};
+
+/// Solana network type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Network {
+    Mainnet,
+    Devnet,
+}
+
+impl Network {
+    pub fn default_endpoints(&self) -> Vec<String> {
+        match self {
+            Network::Mainnet => vec![
+                "https://api.mainnet-beta.solana.com".to_string(),
+                "https://solana.publicnode.com".to_string(),
+            ],
+            Network::Devnet => vec![
+                "https://api.devnet.solana.com".to_string(),
+                "https://devnet.sonic.game".to_string(),
+            ],
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Network::Mainnet => "mainnet-beta",
+            Network::Devnet => "devnet",
+        }
+    }
+}
+
+// StauroX configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub network: Network,
+    pub rpc: RpcConfig,
+    pub monitoring: MonitoringConfig,
+    pub api: ApiConfig,
+    #[serde(default)]
+    pub risk: RiskConfig,
+    /// Named acceptance policies selectable per verify request
+    ///
+    /// Always includes `standard`, which configured policies may override.
+    #[serde(default = "default_policies", deserialize_with = "deserialize_policies")]
+    pub policies: HashMap<String, AcceptancePolicy>,
+    #[serde(default)]
+    pub storage: StorageConfig,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StorageConfig {
+    /// SQLite database file for verification results; nothing is persisted when unset
+    #[serde(default)]
+    pub path: Option<String>,
+}
+
+fn default_policies() -> HashMap<String, AcceptancePolicy> {
+    HashMap::from([("standard".to_string(), AcceptancePolicy::standard())])
+}
+
+fn deserialize_policies<'de, D>(
+    deserializer: D,
+) -> std::result::Result<HashMap<String, AcceptancePolicy>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let configured: HashMap<String, AcceptancePolicy> = HashMap::deserialize(deserializer)?;
+    let mut policies = default_policies();
+    policies.extend(configured);
+    Ok(policies)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcConfig {
+    pub endpoints: Vec<String>,
+    pub consensus_threshold: usize,
+    pub request_timeout_ms: u64,
+    /// Trust weight per endpoint URL; endpoints not listed weigh 1.0
+    #[serde(default)]
+    pub endpoint_weights: HashMap<String, f64>,
+    /// Fraction of total endpoint weight that must agree (e.g. 0.667), if any
+    #[serde(default)]
+    pub weighted_quorum: Option<f64>,
+    /// Transactions fetched concurrently during batch verification
+    #[serde(default = "default_batch_concurrency")]
+    pub batch_concurrency: usize,
+}
+
+fn default_batch_concurrency() -> usize {
+    16
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitoringConfig {
+    pub health_check_interval_ms: u64,
+    pub slot_retention_seconds: u64,
+    pub stale_threshold_seconds: i64,
+    /// How often the vote account set is refreshed for stake confirmation
+    #[serde(default = "default_vote_refresh_interval_ms")]
+    pub vote_refresh_interval_ms: u64,
+    /// How often non-finalized verifications are re-checked for rollbacks
+    #[serde(default = "default_reorg_check_interval_ms")]
+    pub reorg_check_interval_ms: u64,
+}
+
+fn default_vote_refresh_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_reorg_check_interval_ms() -> u64 {
+    5_000
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiConfig {
+    pub websocket_port: u16,
+    pub rest_port: u16,
+    /// Upper bound on how long a wait-for-finality request may block
+    #[serde(default = "default_max_finality_wait_ms")]
+    pub max_finality_wait_ms: u64,
+    /// Most signatures accepted by one batch verification request
+    #[serde(default = "default_max_batch_size")]
+    pub max_batch_size: usize,
+    /// Audit trails kept for retrieval through the API (0 keeps none)
+    #[serde(default = "default_audit_log_size")]
+    pub audit_log_size: usize,
+    /// Verification results kept in the result cache (0 disables caching)
+    #[serde(default = "default_cache_size")]
+    pub cache_size: usize,
+    /// How long results that are not finalized stay cached
+    #[serde(default = "default_cache_ttl_ms")]
+    pub cache_ttl_ms: u64,
+}
+
+fn default_max_finality_wait_ms() -> u64 {
+    120_000
+}
+
+fn default_max_batch_size() -> usize {
+    1000
+}
+
+fn default_audit_log_size() -> usize {
+    1000
+}
+
+fn default_cache_size() -> usize {
+    10_000
+}
+
+fn default_cache_ttl_ms() -> u64 {
+    2_000
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RiskConfig {
+    /// Weight per risk factor label (e.g. "finality"); factors not listed keep their default
+    #[serde(default)]
+    pub weights: HashMap<String, f64>,
+    /// Stricter finality and extra risk for large transfers
+    #[serde(default)]
+    pub amount_tiers: AmountTiers,
+}
+
+/// Requirements for transfers of at least `min_amount`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AmountTier {
+    /// Smallest amount in the tier, in the token's raw units
+    pub min_amount: u64,
+    /// Finality a verified transfer must reach to count as safe
+    pub required_finality: FinalityLevel,
+    /// Raw risk of the "amount" factor for transfers in this tier, in [0, 1]
+    pub risk_weight: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AmountTiers {
+    /// Tiers per bridge, for mints without tiers of their own
+    #[serde(default)]
+    pub bridges: HashMap<BridgeType, Vec<AmountTier>>,
+    /// Tiers per token mint address
+    #[serde(default)]
+    pub mints: HashMap<String, Vec<AmountTier>>,
+}
+
+impl AmountTiers {
+    /// Strictest tier any of the transferred mints reaches
+    ///
+    /// Each mint's total is tiered on its own, by the mint's tiers if it has
+    /// them and otherwise the bridge's. Bridge tiers are in one token's raw
+    /// units, so when the transaction moves several mints they fail closed:
+    /// a mint without tiers of its own gets the strictest bridge tier.
+    pub fn tier_for(&self, parsed: &ParsedTransaction) -> Option<&AmountTier> {
+        let amounts = parsed.amounts_by_mint();
+        let mixed_mints = amounts.len() > 1;
+        let bridge_tiers = self.bridges.get(&parsed.bridge_type);
+
+        amounts
+            .into_iter()
+            .filter_map(|(mint, amount)| match mint.and_then(|mint| self.mints.get(mint)) {
+                Some(tiers) => reached_tier(tiers, amount),
+                None if mixed_mints => bridge_tiers?.iter().max_by(|a, b| strictness(a, b)),
+                None => reached_tier(bridge_tiers?, amount),
+            })
+            .max_by(|a, b| strictness(a, b))
+    }
+
+    fn all(&self) -> impl Iterator<Item = &AmountTier> {
+        self.bridges.values().chain(self.mints.values()).flatten()
+    }
+}
+
+/// Highest tier `amount` reaches
+fn reached_tier(tiers: &[AmountTier], amount: u64) -> Option<&AmountTier> {
+    tiers
+        .iter()
+        .filter(|tier| amount >= tier.min_amount)
+        .max_by_key(|tier| tier.min_amount)
+}
+
+/// Orders tiers by the finality they require, then by risk weight
+fn strictness(a: &AmountTier, b: &AmountTier) -> std::cmp::Ordering {
+    a.required_finality
+        .cmp(&b.required_finality)
+        .then(a.risk_weight.total_cmp(&b.risk_weight))
+}
+
+impl Config {
+    /// The `standard` policy, as configured
+    pub fn standard_policy(&self) -> AcceptancePolicy {
+        self.policies
+            .get("standard")
+            .cloned()
+            .unwrap_or_else(AcceptancePolicy::standard)
+    }
+
+    /// Validate configuration
+    pub fn validate(&self) -> Result<()> {
+        if self.rpc.endpoints.is_empty() {
+            return Err(StauroXError::config("At least one RPC endpoint required"));
+        }
+
+        if self.rpc.consensus_threshold == 0 {
+            return Err(StauroXError::config("Consensus threshold must be > 0"));
+        }
+
+        if self.rpc.consensus_threshold > self.rpc.endpoints.len() {
+            return Err(StauroXError::config(format!(
+                "Consensus threshold ({}) exceeds number of endpoints ({})",
+                self.rpc.consensus_threshold,
+                self.rpc.endpoints.len()
+            )));
+        }
+
+        for (endpoint, weight) in &self.rpc.endpoint_weights {
+            if !self.rpc.endpoints.contains(endpoint) {
+                return Err(StauroXError::config(format!(
+                    "Weight given for unknown endpoint: {}",
+                    endpoint
+                )));
+            }
+
+            if !weight.is_finite() || *weight <= 0.0 {
+                return Err(StauroXError::config(format!(
+                    "Endpoint weight must be > 0 (got {} for {})",
+                    weight, endpoint
+                )));
+            }
+        }
+
+        if let Some(fraction) = self.rpc.weighted_quorum {
+            if !(fraction > 0.0 && fraction <= 1.0) {
+                return Err(StauroXError::config(format!(
+                    "Weighted quorum must be in (0, 1] (got {})",
+                    fraction
+                )));
+            }
+        }
+
+        if self.rpc.batch_concurrency == 0 {
+            return Err(StauroXError::config("Batch concurrency must be > 0"));
+        }
+
+        if self.monitoring.health_check_interval_ms == 0 {
+            return Err(StauroXError::config(
+                "Health check interval must be > 0",
+            ));
+        }
+
+        if self.monitoring.vote_refresh_interval_ms == 0 {
+            return Err(StauroXError::config(
+                "Vote refresh interval must be > 0",
+            ));
+        }
+
+        if self.monitoring.reorg_check_interval_ms == 0 {
+            return Err(StauroXError::config(
+                "Reorg check interval must be > 0",
+            ));
+        }
+
+        if self.api.max_finality_wait_ms == 0 {
+            return Err(StauroXError::config(
+                "Max finality wait must be > 0",
+            ));
+        }
+
+        if self.api.max_batch_size == 0 {
+            return Err(StauroXError::config("Max batch size must be > 0"));
+        }
+
+        for (factor, weight) in &self.risk.weights {
+            if !weight.is_finite() || *weight < 0.0 {
+                return Err(StauroXError::config(format!(
+                    "Risk weight must be >= 0 (got {} for {})",
+                    weight, factor
+                )));
+            }
+        }
+
+        for (name, policy) in &self.policies {
+            if let Some(max_risk) = policy.max_risk {
+                if !(0.0..=1.0).contains(&max_risk) {
+                    return Err(StauroXError::config(format!(
+                        "Policy {} max risk must be in [0, 1] (got {})",
+                        name, max_risk
+                    )));
+                }
+            }
+        }
+
+        for tier in self.risk.amount_tiers.all() {
+            if !(0.0..=1.0).contains(&tier.risk_weight) {
+                return Err(StauroXError::config(format!(
+                    "Amount tier risk weight must be in [0, 1] (got {} from {})",
+                    tier.risk_weight, tier.min_amount
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn health_check_interval(&self) -> Duration {
+        Duration::from_millis(self.monitoring.health_check_interval_ms)
+    }
+
+    pub fn vote_refresh_interval(&self) -> Duration {
+        Duration::from_millis(self.monitoring.vote_refresh_interval_ms)
+    }
+
+    pub fn reorg_check_interval(&self) -> Duration {
+        Duration::from_millis(self.monitoring.reorg_check_interval_ms)
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        Duration::from_millis(self.rpc.request_timeout_ms)
+    }
+
+    pub fn max_finality_wait(&self) -> Duration {
+        Duration::from_millis(self.api.max_finality_wait_ms)
+    }
+
+    pub fn cache_ttl(&self) -> Duration {
+        Duration::from_millis(self.api.cache_ttl_ms)
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self::devnet()
+    }
+}
+
+impl Config {
+    /// Create devnet configuration (safer for testing)
+    pub fn devnet() -> Self {
+        let network = Network::Devnet;
+        Self {
+            network,
+            rpc: RpcConfig {
+                endpoints: network.default_endpoints(),
+                consensus_threshold: 2,
+                request_timeout_ms: 5000,
+                endpoint_weights: HashMap::new(),
+                weighted_quorum: None,
+                batch_concurrency: default_batch_concurrency(),
+            },
+            monitoring: MonitoringConfig {
+                health_check_interval_ms: 400,
+                slot_retention_seconds: 30,
+                stale_threshold_seconds: 5,
+                vote_refresh_interval_ms: default_vote_refresh_interval_ms(),
+                reorg_check_interval_ms: default_reorg_check_interval_ms(),
+            },
+            api: ApiConfig {
+                websocket_port: 8080,
+                rest_port: 8081,
+                max_finality_wait_ms: default_max_finality_wait_ms(),
+                max_batch_size: default_max_batch_size(),
+                audit_log_size: default_audit_log_size(),
+                cache_size: default_cache_size(),
+                cache_ttl_ms: default_cache_ttl_ms(),
+            },
+            risk: RiskConfig::default(),
+            policies: default_policies(),
+            storage: StorageConfig::default(),
+        }
+    }
+
+    /// Create mainnet configuration (production)
+    pub fn mainnet() -> Self {
+        let network = Network::Mainnet;
+        Self {
+            network,
+            rpc: RpcConfig {
+                endpoints: network.default_endpoints(),
+                consensus_threshold: 1,
+                request_timeout_ms: 10000,
+                endpoint_weights: HashMap::new(),
+                weighted_quorum: None,
+                batch_concurrency: default_batch_concurrency(),
+            },
+            monitoring: MonitoringConfig {
+                health_check_interval_ms: 2000,
+                slot_retention_seconds: 30,
+                stale_threshold_seconds: 5,
+                vote_refresh_interval_ms: default_vote_refresh_interval_ms(),
+                reorg_check_interval_ms: default_reorg_check_interval_ms(),
+            },
+            api: ApiConfig {
+                websocket_port: 8080,
+                rest_port: 8081,
+                max_finality_wait_ms: default_max_finality_wait_ms(),
+                max_batch_size: default_max_batch_size(),
+                audit_log_size: default_audit_log_size(),
+                cache_size: default_cache_size(),
+                cache_ttl_ms: default_cache_ttl_ms(),
+            },
+            risk: RiskConfig::default(),
+            policies: default_policies(),
+            storage: StorageConfig::default(),
+        }
+    }
+
+    /// Create custom configuration with specific endpoints
+    pub fn custom(network: Network, endpoints: Vec<String>) -> Self {
+        let mut config = match network {
+            Network::Mainnet => Self::mainnet(),
+            Network::Devnet => Self::devnet(),
+        };
+        config.rpc.consensus_threshold = config.rpc.consensus_threshold.min(endpoints.len());
+        config.rpc.endpoint_weights.retain(|url, _| endpoints.contains(url));
+        config.rpc.endpoints = endpoints;
+        config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let config = Config::default();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.network, Network::Devnet);
+    }
+
+    #[test]
+    fn test_devnet_config() {
+        let config = Config::devnet();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.network, Network::Devnet);
+        assert_eq!(config.rpc.endpoints.len(), 2);
+    }
+
+    #[test]
+    fn test_mainnet_config() {
+        let config = Config::mainnet();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.network, Network::Mainnet);
+        assert_eq!(config.rpc.endpoints.len(), 2);
+    }
+
+    #[test]
+    fn test_custom_config() {
+        let custom_endpoints = vec!["https://custom.rpc".to_string()];
+        let config = Config::custom(Network::Devnet, custom_endpoints.clone());
+        assert!(config.validate().is_ok());
+        assert_eq!(config.rpc.endpoints, custom_endpoints);
+    }
+
+    #[test]
+    fn test_network_endpoints() {
+        let devnet_endpoints = Network::Devnet.default_endpoints();
+        let mainnet_endpoints = Network::Mainnet.default_endpoints();
+        
+        assert!(!devnet_endpoints.is_empty());
+        assert!(!mainnet_endpoints.is_empty());
+        assert_ne!(devnet_endpoints, mainnet_endpoints);
+    }
+
+    #[test]
+    fn test_invalid_consensus_threshold() {
+        let mut config = Config::default();
+        config.rpc.consensus_threshold = 10;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_endpoint_weights() {
+        let mut config = Config::default();
+        let trusted = config.rpc.endpoints[0].clone();
+        config.rpc.endpoint_weights.insert(trusted.clone(), 3.0);
+        config.rpc.weighted_quorum = Some(2.0 / 3.0);
+        assert!(config.validate().is_ok());
+
+        config.rpc.endpoint_weights.insert(trusted, 0.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_weighted_quorum() {
+        let mut config = Config::default();
+        config.rpc.weighted_quorum = Some(1.5);
+        assert!(config.validate().is_err());
+
+        config.rpc.weighted_quorum = None;
+        config
+            .rpc
+            .endpoint_weights
+            .insert("https://unknown.rpc".to_string(), 2.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_vote_refresh_interval() {
+        let mut config = Config::default();
+        assert_eq!(config.vote_refresh_interval(), Duration::from_millis(10_000));
+
+        config.monitoring.vote_refresh_interval_ms = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_invalid_risk_weight() {
+        let mut config = Config::default();
+        config.risk.weights.insert("finality".to_string(), 0.3);
+        assert!(config.validate().is_ok());
+
+        config.risk.weights.insert("consensus".to_string(), -1.0);
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_amount_tier_lookup() {
+        let tier = |min_amount, required_finality| AmountTier {
+            min_amount,
+            required_finality,
+            risk_weight: 0.5,
+        };
+        let mut tiers = AmountTiers::default();
+        tiers.bridges.insert(
+            BridgeType::Wormhole,
+            vec![tier(1_000, FinalityLevel::Safe), tier(1_000_000, FinalityLevel::UltraSafe)],
+        );
+        tiers.mints.insert("usdc".to_string(), vec![tier(10, FinalityLevel::UltraSafe)]);
+
+        let transfer = |amount| ParsedTransaction::new(
+            BridgeType::Wormhole,
+            crate::parsers::BridgeInstruction::TransferNative {
+                amount,
+                target_chain: 2,
+                recipient: vec![],
+            },
+        );
+
+        assert!(tiers.tier_for(&transfer(10)).is_none());
+        assert_eq!(tiers.tier_for(&transfer(5_000)).unwrap().min_amount, 1_000);
+        assert_eq!(tiers.tier_for(&transfer(5_000_000)).unwrap().min_amount, 1_000_000);
+
+        let usdc = transfer(50).with_mint(Some("usdc".to_string()));
+        assert_eq!(tiers.tier_for(&usdc).unwrap().min_amount, 10);
+
+        // Each mint is tiered on its own amount; the untiered one fails closed
+        let call = |outer_index, amount, mint: &str| {
+            let location = crate::parsers::InstructionLocation { outer_index, inner_index: None };
+            let call = transfer(amount).instructions.remove(0);
+            crate::parsers::BridgeCall::new(location, call.instruction)
+                .with_mint(Some(mint.to_string()))
+        };
+        let mixed = ParsedTransaction::from_calls(
+            BridgeType::Wormhole,
+            vec![call(0, 5, "usdc"), call(1, 2_000, "wsol")],
+        );
+        let tier = tiers.tier_for(&mixed).unwrap();
+        assert_eq!(tier.required_finality, FinalityLevel::UltraSafe);
+        assert_eq!(tier.min_amount, 1_000_000);
+
+        let mixed = ParsedTransaction::from_calls(
+            BridgeType::Wormhole,
+            vec![call(0, 50, "usdc"), call(1, 20, "usdc")],
+        );
+        assert_eq!(tiers.tier_for(&mixed).unwrap().min_amount, 10);
+    }
+
+    #[test]
+    fn test_policies() {
+        let mut config = Config::default();
+        assert!(config.policies.contains_key("standard"));
+
+        config.policies.insert(
+            "strict".to_string(),
+            AcceptancePolicy {
+                max_risk: Some(1.5),
+                ..AcceptancePolicy::default()
+            },
+        );
+        assert!(config.validate().is_err());
+
+        // Configured policies keep the built-in standard unless they override it
+        let mut value = serde_json::to_value(Config::default()).unwrap();
+        value["policies"] = serde_json::json!({ "strict": { "max_risk": 0.1 } });
+        let config: Config = serde_json::from_value(value.clone()).unwrap();
+        assert!(config.policies.contains_key("strict"));
+        assert_eq!(config.standard_policy(), AcceptancePolicy::standard());
+
+        value["policies"] = serde_json::json!({ "standard": { "max_risk": 0.1 } });
+        let config: Config = serde_json::from_value(value).unwrap();
+        assert_eq!(config.standard_policy().max_risk, Some(0.1));
+    }
+
+    #[test]
+    fn test_empty_endpoints() {
+        let mut config = Config::default();
+        config.rpc.endpoints.clear();
+        assert!(config.validate().is_err());
+    }
 }
\ No newline at end of file
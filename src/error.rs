@@ -1,60 +1,147 @@
-use thiserror::Error;
-
-/// All possible errors in the StauroX system
-#[derive(Debug, Error)]
-pub enum StauroXError {
-    #[error("RPC error: {0}")]
-    Rpc(#[from] solana_client::client_error::ClientError),
-
-    #[error("Consensus failed: {message}")]
-    ConsensusFailure {
-        message: String,
-        responses: usize,
-        required: usize,
-    },
-
-    #[error("Network health check failed: {0}")]
-    HealthCheck(String),
-
-    #[error("Configuration error: {0}")]
-    Config(String),
-
-    #[error("Signature parse error: {0}")]
-    SignatureParse(#[from] solana_sdk::signature::ParseSignatureError),
-
-    #[error("Transaction verification failed: {0}")]
-    Verification(String),
-
-    #[error("Serialization error: {0}")]
-    Serialization(#[from] serde_json::Error),
-
-    #[error("IO error: {0}")]
-    Io(#[from] std::io::Error),
-}
-
-pub type Result<T> = std::result::Result<T, StauroXError>;
-
-impl StauroXError {
-    pub fn consensus_failure(responses: usize, required: usize) -> Self {
-        Self::ConsensusFailure {
-            message: format!(
-                "Insufficient consensus: {}/{} responses",
-                responses, required
-            ),
-            responses,
-            required,
-        }
-    }
-
-    pub fn health_check(msg: impl Into<String>) -> Self {
-        Self::HealthCheck(msg.into())
-    }
-
-    pub fn config(msg: impl Into<String>) -> Self {
-        Self::Config(msg.into())
-    }
-
-    pub fn verification(msg: impl Into<String>) -> Self {
-        Self::Verification(msg.into())
-    }
+use thiserror::Error;
+
+use crate::types::FinalityLevel;
+
+/// All possible errors in the StauroX system
+#[derive(Debug, Error)]
+pub enum StauroXError {
+    #[error("RPC error: {0}")]
+    Rpc(Box<solana_client::client_error::ClientError>),
+
+    #[error("RPC request to {endpoint} timed out after {timeout_ms}ms")]
+    RpcTimeout { endpoint: String, timeout_ms: u64 },
+
+    #[error("Consensus failed: {message}")]
+    ConsensusFailure {
+        message: String,
+        responses: usize,
+        required: usize,
+        /// Trust weight behind the best-supported answer
+        weight: f64,
+        /// Weight needed when a weighted quorum is configured
+        required_weight: Option<f64>,
+        dissenting: Vec<String>,
+    },
+
+    #[error("Network halted - cannot verify transactions")]
+    NetworkHalted,
+
+    #[error("Network health check failed: {0}")]
+    HealthCheck(String),
+
+    #[error("Configuration error: {0}")]
+    Config(String),
+
+    #[error("Signature parse error: {0}")]
+    SignatureParse(#[from] solana_sdk::signature::ParseSignatureError),
+
+    #[error("Transaction verification failed: {0}")]
+    Verification(String),
+
+    #[error("Finality {target:?} not reached within {timeout_ms}ms (last seen: {reached:?})")]
+    FinalityTimeout {
+        target: FinalityLevel,
+        reached: FinalityLevel,
+        timeout_ms: u64,
+    },
+
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Storage error: {0}")]
+    Storage(#[from] rusqlite::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, StauroXError>;
+
+impl From<solana_client::client_error::ClientError> for StauroXError {
+    fn from(err: solana_client::client_error::ClientError) -> Self {
+        Self::Rpc(Box::new(err))
+    }
+}
+
+impl StauroXError {
+    pub fn rpc_timeout(endpoint: impl Into<String>, timeout: std::time::Duration) -> Self {
+        Self::RpcTimeout {
+            endpoint: endpoint.into(),
+            timeout_ms: timeout.as_millis() as u64,
+        }
+    }
+
+    pub fn consensus_failure(
+        responses: usize,
+        required: usize,
+        weight: f64,
+        required_weight: Option<f64>,
+    ) -> Self {
+        let weight_summary = required_weight
+            .map(|req| format!(", weight {:.2}/{:.2}", weight, req))
+            .unwrap_or_default();
+
+        Self::ConsensusFailure {
+            message: format!(
+                "Insufficient consensus: {}/{} responses{}",
+                responses, required, weight_summary
+            ),
+            responses,
+            required,
+            weight,
+            required_weight,
+            dissenting: Vec::new(),
+        }
+    }
+
+    pub fn consensus_disagreement(
+        agreeing: usize,
+        required: usize,
+        weight: f64,
+        required_weight: Option<f64>,
+        dissenting: Vec<String>,
+    ) -> Self {
+        let weight_summary = required_weight
+            .map(|req| format!(", weight {:.2}/{:.2}", weight, req))
+            .unwrap_or_default();
+
+        Self::ConsensusFailure {
+            message: format!(
+                "Insufficient agreement: {}/{} matching responses{}, dissenting: [{}]",
+                agreeing,
+                required,
+                weight_summary,
+                dissenting.join(", ")
+            ),
+            responses: agreeing,
+            required,
+            weight,
+            required_weight,
+            dissenting,
+        }
+    }
+
+    pub fn health_check(msg: impl Into<String>) -> Self {
+        Self::HealthCheck(msg.into())
+    }
+
+    pub fn config(msg: impl Into<String>) -> Self {
+        Self::Config(msg.into())
+    }
+
+    pub fn verification(msg: impl Into<String>) -> Self {
+        Self::Verification(msg.into())
+    }
+
+    pub fn finality_timeout(
+        target: FinalityLevel,
+        reached: FinalityLevel,
+        timeout: std::time::Duration,
+    ) -> Self {
+        Self::FinalityTimeout {
+            target,
+            reached,
+            timeout_ms: timeout.as_millis() as u64,
+        }
+    }
 }
\ No newline at end of file
@@ -1,18 +1,19 @@
-pub mod api;
-pub mod config;
-pub mod error;
-pub mod monitor;
-pub mod parsers;
-pub mod rpc;
-pub mod service;
-pub mod types;
-pub mod verification;
-
-// Re-exports
-pub use api::{ApiState, WsState};
-pub use config::{Config, Network};
-pub use error::{Result, StauroXError};
-pub use parsers::{BridgeInstruction, BridgeType, ParsedTransaction, TransactionParser};  // NEW
-pub use service::VerificationService;
-pub use types::{FinalityLevel, NetworkHealth, SlotObservation, VerificationResult};
+pub mod api;
+pub mod config;
+pub mod error;
+pub mod monitor;
+pub mod parsers;
+pub mod rpc;
+pub mod service;
+pub mod storage;
+pub mod types;
+pub mod verification;
+
+// Re-exports
+pub use api::{ApiState, WsState};
+pub use config::{Config, Network};
+pub use error::{Result, StauroXError};
+pub use parsers::{BridgeInstruction, BridgeParser, BridgeType, ParsedTransaction, TransactionParser};  // NEW
+pub use service::VerificationService;
+pub use types::{FinalityLevel, NetworkHealth, SlotObservation, VerificationResult};
 pub use verification::VerificationEngine;
\ No newline at end of file
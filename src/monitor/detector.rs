@@ -1,147 +1,481 @@
-use std::collections::HashMap;
-
-use crate::types::{NetworkHealth, SlotObservation};
-
-// Constants for network health detection
-const FORK_SUPPORT_THRESHOLD: f64 = 30.0;
-const HEALTHY_LAG_TOLERANCE: u64 = 2;
-
-// Detector for network forks and halts
-pub struct NetworkDetector {
-    stale_threshold_secs: i64,
-}
-
-impl NetworkDetector {
-    pub fn new(stale_threshold_secs: i64) -> Self {
-        Self {
-            stale_threshold_secs,
-        }
-    }
-
-    // Determine network health from observations
-    pub fn detect_health(
-        &self,
-        observations: &HashMap<String, SlotObservation>,
-    ) -> NetworkHealth {
-        if observations.is_empty() {
-            return NetworkHealth::Halted;
-        }
-
-        if self.all_observations_stale(observations) {
-            return NetworkHealth::Halted;
-        }
-
-        if self.has_significant_fork(observations) {
-            return NetworkHealth::Forked;
-        }
-
-        NetworkHealth::Healthy
-    }
-
-    fn all_observations_stale(&self, observations: &HashMap<String, SlotObservation>) -> bool {
-        observations
-            .values()
-            .all(|obs| obs.is_stale(self.stale_threshold_secs))
-    }
-
-    fn has_significant_fork(&self, observations: &HashMap<String, SlotObservation>) -> bool {
-        let slot_groups = self.group_by_slot(observations);
-
-        if self.within_healthy_tolerance(&slot_groups) {
-            return false;
-        }
-
-        let total_sources = observations.len();
-        let significant_forks = slot_groups
-            .values()
-            .filter(|sources| {
-                let support_percent = (**sources as f64 / total_sources as f64) * 100.0;
-                support_percent > FORK_SUPPORT_THRESHOLD
-            })
-            .count();
-
-        significant_forks > 1
-    }
-
-    fn group_by_slot(&self, observations: &HashMap<String, SlotObservation>) -> HashMap<u64, usize> {
-        let mut slot_counts: HashMap<u64, usize> = HashMap::new();
-        for obs in observations.values() {
-            *slot_counts.entry(obs.slot).or_insert(0) += 1;
-        }
-        slot_counts
-    }
-
-    fn within_healthy_tolerance(&self, slot_groups: &HashMap<u64, usize>) -> bool {
-        if slot_groups.len() <= 1 {
-            return true;
-        }
-
-        let slots: Vec<u64> = slot_groups.keys().copied().collect();
-        let min_slot = *slots.iter().min().unwrap();
-        let max_slot = *slots.iter().max().unwrap();
-
-        (max_slot - min_slot) <= HEALTHY_LAG_TOLERANCE
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::Utc;
-
-    fn create_observation(slot: u64, source: &str) -> (String, SlotObservation) {
-        (
-            source.to_string(),
-            SlotObservation {
-                slot,
-                source: source.to_string(),
-                timestamp: Utc::now(),
-                stake_percent: Some(25.0),
-            },
-        )
-    }
-
-    #[test]
-    fn test_healthy_network() {
-        let detector = NetworkDetector::new(5);
-        let mut obs = HashMap::new();
-        
-        for i in 0..4 {
-            let (source, observation) = create_observation(12345, &format!("rpc{}", i));
-            obs.insert(source, observation);
-        }
-
-        assert_eq!(detector.detect_health(&obs), NetworkHealth::Healthy);
-    }
-
-    #[test]
-    fn test_forked_network() {
-        let detector = NetworkDetector::new(5);
-        let mut obs = HashMap::new();
-
-        for i in 0..2 {
-            let (source, observation) = create_observation(100, &format!("rpc{}", i));
-            obs.insert(source, observation);
-        }
-        for i in 2..4 {
-            let (source, observation) = create_observation(105, &format!("rpc{}", i));
-            obs.insert(source, observation);
-        }
-
-        assert_eq!(detector.detect_health(&obs), NetworkHealth::Forked);
-    }
-
-    #[test]
-    fn test_halted_network() {
-        let detector = NetworkDetector::new(5);
-        let mut obs = HashMap::new();
-
-        for i in 0..4 {
-            let mut observation = SlotObservation::new(12345, format!("rpc{}", i));
-            observation.timestamp = Utc::now() - chrono::Duration::seconds(10);
-            obs.insert(format!("rpc{}", i), observation);
-        }
-
-        assert_eq!(detector.detect_health(&obs), NetworkHealth::Halted);
-    }
+use std::collections::HashMap;
+
+use chrono::Utc;
+
+use crate::types::{ForkBranch, ForkReport, NetworkHealth, SlotObservation, SourceLag};
+
+// Constants for network health detection
+const FORK_SUPPORT_THRESHOLD: f64 = 30.0;
+const HEALTHY_LAG_TOLERANCE: u64 = 2;
+// A source this far behind the median slot is lagging rather than jittering
+const LAGGING_SLOT_THRESHOLD: u64 = 10;
+
+// Solana targets ~400ms slots; below half that rate the network is slow
+const EXPECTED_SLOTS_PER_SEC: f64 = 2.5;
+const SLOW_RATE_FRACTION: f64 = 0.5;
+
+// Detector for network forks and halts
+pub struct NetworkDetector {
+    stale_threshold_secs: i64,
+}
+
+impl NetworkDetector {
+    pub fn new(stale_threshold_secs: i64) -> Self {
+        Self {
+            stale_threshold_secs,
+        }
+    }
+
+    // Determine network health from observations
+    pub fn detect_health(
+        &self,
+        observations: &HashMap<String, SlotObservation>,
+    ) -> NetworkHealth {
+        if observations.is_empty() {
+            return NetworkHealth::Halted;
+        }
+
+        if self.all_observations_stale(observations) {
+            return NetworkHealth::Halted;
+        }
+
+        if self.detect_fork(observations).is_some() {
+            return NetworkHealth::Forked;
+        }
+
+        // A lagging minority is degraded service, not a competing branch
+        let lagging: Vec<String> = self
+            .source_lags(observations)
+            .into_iter()
+            .filter(|lag| self.is_lagging(lag))
+            .map(|lag| lag.source)
+            .collect();
+        let fresh_count = self.fresh_observations(observations).len();
+        let lagging_is_minority = lagging.len() * 2 < fresh_count;
+
+        let excluded: &[String] = if lagging_is_minority { &lagging } else { &[] };
+        if self.has_significant_fork(observations, excluded) {
+            return NetworkHealth::Forked;
+        }
+
+        if !lagging.is_empty() {
+            return NetworkHealth::Degraded;
+        }
+
+        NetworkHealth::Healthy
+    }
+
+    // How far each fresh source trails the median slot
+    //
+    // The median keeps a single source racing ahead from making everyone
+    // else look behind. Sorted with the furthest behind first.
+    pub fn source_lags(&self, observations: &HashMap<String, SlotObservation>) -> Vec<SourceLag> {
+        let fresh = self.fresh_observations(observations);
+        if fresh.is_empty() {
+            return Vec::new();
+        }
+
+        let mut slots: Vec<u64> = fresh.iter().map(|obs| obs.slot).collect();
+        slots.sort_unstable();
+        let median_slot = slots[slots.len() / 2];
+
+        let mut lags: Vec<SourceLag> = fresh
+            .iter()
+            .map(|obs| SourceLag {
+                source: obs.source.clone(),
+                slot: obs.slot,
+                lag_slots: median_slot.saturating_sub(obs.slot),
+            })
+            .collect();
+        lags.sort_by(|a, b| b.lag_slots.cmp(&a.lag_slots).then_with(|| a.source.cmp(&b.source)));
+        lags
+    }
+
+    pub fn is_lagging(&self, lag: &SourceLag) -> bool {
+        lag.lag_slots > LAGGING_SLOT_THRESHOLD
+    }
+
+    // Judge health from slot production rates (slots/sec per source)
+    //
+    // Uses the median over fresh sources so one stuck endpoint can't drag the
+    // network into a halt. Sources without enough history are left out.
+    pub fn detect_progression(
+        &self,
+        observations: &HashMap<String, SlotObservation>,
+        slot_rates: &HashMap<String, f64>,
+    ) -> NetworkHealth {
+        let mut rates: Vec<f64> = self
+            .fresh_observations(observations)
+            .iter()
+            .filter_map(|obs| slot_rates.get(&obs.source).copied())
+            .collect();
+
+        if rates.is_empty() {
+            return NetworkHealth::Healthy;
+        }
+
+        rates.sort_by(|a, b| a.total_cmp(b));
+        let median = rates[rates.len() / 2];
+
+        if median <= 0.0 {
+            NetworkHealth::Halted
+        } else if median < EXPECTED_SLOTS_PER_SEC * SLOW_RATE_FRACTION {
+            NetworkHealth::Slow
+        } else {
+            NetworkHealth::Healthy
+        }
+    }
+
+    // Find fresh sources that disagree on the block at the same slot
+    //
+    // Differing tips can just be lag; a different block (hash or parent) at
+    // one sampled slot can't. When several slots are contested the highest
+    // one is reported.
+    pub fn detect_fork(
+        &self,
+        observations: &HashMap<String, SlotObservation>,
+    ) -> Option<ForkReport> {
+        let mut by_slot: HashMap<u64, Vec<&SlotObservation>> = HashMap::new();
+        for obs in self.fresh_observations(observations) {
+            if let (Some(block_slot), Some(_)) = (obs.block_slot, &obs.blockhash) {
+                by_slot.entry(block_slot).or_default().push(obs);
+            }
+        }
+
+        by_slot
+            .into_iter()
+            .filter_map(|(slot, observed)| {
+                let branches = self.group_by_blockhash(&observed);
+                (branches.len() > 1).then(|| ForkReport {
+                    slot,
+                    branches,
+                    detected_at: Utc::now(),
+                })
+            })
+            .max_by_key(|report| report.slot)
+    }
+
+    fn group_by_blockhash(&self, observations: &[&SlotObservation]) -> Vec<ForkBranch> {
+        let mut branches: Vec<ForkBranch> = Vec::new();
+        for obs in observations {
+            let Some(blockhash) = &obs.blockhash else {
+                continue;
+            };
+            let same_block = |b: &&mut ForkBranch| {
+                &b.blockhash == blockhash && b.parent_slot == obs.parent_slot
+            };
+            match branches.iter_mut().find(same_block) {
+                Some(branch) => branch.sources.push(obs.source.clone()),
+                None => branches.push(ForkBranch {
+                    blockhash: blockhash.clone(),
+                    parent_slot: obs.parent_slot,
+                    sources: vec![obs.source.clone()],
+                }),
+            }
+        }
+
+        // Stable output: biggest branch first, sources sorted
+        for branch in &mut branches {
+            branch.sources.sort();
+        }
+        branches.sort_by(|a, b| {
+            b.sources
+                .len()
+                .cmp(&a.sources.len())
+                .then_with(|| a.blockhash.cmp(&b.blockhash))
+        });
+        branches
+    }
+
+    fn all_observations_stale(&self, observations: &HashMap<String, SlotObservation>) -> bool {
+        observations
+            .values()
+            .all(|obs| obs.is_stale(self.stale_threshold_secs))
+    }
+
+    // Only fresh observations take part; a source whose polls keep failing
+    // goes stale and drops out rather than posing as a lagging branch
+    fn has_significant_fork(
+        &self,
+        observations: &HashMap<String, SlotObservation>,
+        excluded: &[String],
+    ) -> bool {
+        let fresh: Vec<&SlotObservation> = self
+            .fresh_observations(observations)
+            .into_iter()
+            .filter(|obs| !excluded.contains(&obs.source))
+            .collect();
+        let slot_groups = self.group_by_slot(&fresh);
+
+        if self.within_healthy_tolerance(&slot_groups) {
+            return false;
+        }
+
+        let total_sources = fresh.len();
+        let significant_forks = slot_groups
+            .values()
+            .filter(|sources| {
+                let support_percent = (**sources as f64 / total_sources as f64) * 100.0;
+                support_percent > FORK_SUPPORT_THRESHOLD
+            })
+            .count();
+
+        significant_forks > 1
+    }
+
+    fn fresh_observations<'a>(
+        &self,
+        observations: &'a HashMap<String, SlotObservation>,
+    ) -> Vec<&'a SlotObservation> {
+        observations
+            .values()
+            .filter(|obs| !obs.is_stale(self.stale_threshold_secs))
+            .collect()
+    }
+
+    fn group_by_slot(&self, observations: &[&SlotObservation]) -> HashMap<u64, usize> {
+        let mut slot_counts: HashMap<u64, usize> = HashMap::new();
+        for obs in observations {
+            *slot_counts.entry(obs.slot).or_insert(0) += 1;
+        }
+        slot_counts
+    }
+
+    fn within_healthy_tolerance(&self, slot_groups: &HashMap<u64, usize>) -> bool {
+        if slot_groups.len() <= 1 {
+            return true;
+        }
+
+        let slots: Vec<u64> = slot_groups.keys().copied().collect();
+        let min_slot = *slots.iter().min().unwrap();
+        let max_slot = *slots.iter().max().unwrap();
+
+        (max_slot - min_slot) <= HEALTHY_LAG_TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn create_observation(slot: u64, source: &str) -> (String, SlotObservation) {
+        (
+            source.to_string(),
+            SlotObservation {
+                slot,
+                source: source.to_string(),
+                timestamp: Utc::now(),
+                stake_percent: Some(25.0),
+                block_slot: None,
+                blockhash: None,
+                parent_slot: None,
+            },
+        )
+    }
+
+    fn create_block_observation(
+        slot: u64,
+        source: &str,
+        blockhash: &str,
+        parent_slot: u64,
+    ) -> (String, SlotObservation) {
+        (
+            source.to_string(),
+            SlotObservation::new(slot, source).with_block(slot, blockhash, parent_slot),
+        )
+    }
+
+    #[test]
+    fn test_healthy_network() {
+        let detector = NetworkDetector::new(5);
+        let mut obs = HashMap::new();
+        
+        for i in 0..4 {
+            let (source, observation) = create_observation(12345, &format!("rpc{}", i));
+            obs.insert(source, observation);
+        }
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Healthy);
+    }
+
+    #[test]
+    fn test_forked_network() {
+        let detector = NetworkDetector::new(5);
+        let mut obs = HashMap::new();
+
+        for i in 0..2 {
+            let (source, observation) = create_observation(100, &format!("rpc{}", i));
+            obs.insert(source, observation);
+        }
+        for i in 2..4 {
+            let (source, observation) = create_observation(105, &format!("rpc{}", i));
+            obs.insert(source, observation);
+        }
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Forked);
+    }
+
+    #[test]
+    fn test_blockhash_fork_at_same_slot() {
+        let detector = NetworkDetector::new(5);
+        let obs: HashMap<_, _> = [
+            create_block_observation(500, "rpc0", "hashA", 499),
+            create_block_observation(500, "rpc1", "hashA", 499),
+            create_block_observation(500, "rpc2", "hashB", 498),
+            create_block_observation(501, "rpc3", "hashC", 500),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Forked);
+
+        let report = detector.detect_fork(&obs).unwrap();
+        assert_eq!(report.slot, 500);
+        assert_eq!(report.branches.len(), 2);
+        assert_eq!(report.branches[0].blockhash, "hashA");
+        assert_eq!(report.branches[0].sources, vec!["rpc0", "rpc1"]);
+        assert_eq!(report.branches[1].sources, vec!["rpc2"]);
+        assert_eq!(report.branches[1].parent_slot, Some(498));
+    }
+
+    #[test]
+    fn test_matching_blockhashes_are_healthy() {
+        let detector = NetworkDetector::new(5);
+        let obs: HashMap<_, _> = [
+            create_block_observation(500, "rpc0", "hashA", 499),
+            create_block_observation(500, "rpc1", "hashA", 499),
+            create_block_observation(501, "rpc2", "hashB", 500),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(detector.detect_fork(&obs).is_none());
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Healthy);
+    }
+
+    #[test]
+    fn test_fork_found_at_common_slot_below_tips() {
+        let detector = NetworkDetector::new(5);
+        let sampled = |tip, source: &str, blockhash, parent_slot| {
+            let obs = SlotObservation::new(tip, source).with_block(600, blockhash, parent_slot);
+            (source.to_string(), obs)
+        };
+        let obs: HashMap<_, _> = [
+            sampled(604, "rpc0", "hashA", 599),
+            sampled(603, "rpc1", "hashA", 599),
+            sampled(600, "rpc2", "hashA", 598),
+        ]
+        .into_iter()
+        .collect();
+
+        // Same blockhash on a different parent is still another block
+        let report = detector.detect_fork(&obs).unwrap();
+        assert_eq!(report.slot, 600);
+        assert_eq!(report.branches.len(), 2);
+        assert_eq!(report.branches[1].sources, vec!["rpc2"]);
+    }
+
+    #[test]
+    fn test_stale_source_is_not_a_fork() {
+        let detector = NetworkDetector::new(5);
+        let mut obs = HashMap::new();
+
+        for i in 0..3 {
+            let (source, observation) = create_observation(200, &format!("rpc{}", i));
+            obs.insert(source, observation);
+        }
+
+        // rpc3 stopped answering a while ago at a much older slot
+        let mut dead = SlotObservation::new(150, "rpc3");
+        dead.timestamp = Utc::now() - chrono::Duration::seconds(10);
+        obs.insert("rpc3".to_string(), dead);
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Healthy);
+    }
+
+    fn rates(values: &[f64]) -> HashMap<String, f64> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, rate)| (format!("rpc{}", i), *rate))
+            .collect()
+    }
+
+    fn fresh_sources(count: usize) -> HashMap<String, SlotObservation> {
+        (0..count)
+            .map(|i| create_observation(12345, &format!("rpc{}", i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_progression_states() {
+        let detector = NetworkDetector::new(5);
+        let obs = fresh_sources(3);
+
+        assert_eq!(detector.detect_progression(&obs, &rates(&[2.5, 2.4, 2.6])), NetworkHealth::Healthy);
+        assert_eq!(detector.detect_progression(&obs, &rates(&[0.5, 0.4, 0.6])), NetworkHealth::Slow);
+        assert_eq!(detector.detect_progression(&obs, &rates(&[0.0, 0.0, 0.0])), NetworkHealth::Halted);
+        assert_eq!(detector.detect_progression(&obs, &HashMap::new()), NetworkHealth::Healthy);
+    }
+
+    #[test]
+    fn test_single_stuck_source_is_not_a_halt() {
+        let detector = NetworkDetector::new(5);
+        let obs = fresh_sources(3);
+
+        assert_eq!(detector.detect_progression(&obs, &rates(&[0.0, 2.5, 2.5])), NetworkHealth::Healthy);
+    }
+
+    #[test]
+    fn test_lagging_minority_is_degraded() {
+        let detector = NetworkDetector::new(5);
+        let obs: HashMap<_, _> = [
+            create_observation(1000, "rpc0"),
+            create_observation(1001, "rpc1"),
+            create_observation(1000, "rpc2"),
+            create_observation(1001, "rpc3"),
+            create_observation(960, "rpc4"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Degraded);
+
+        let lags = detector.source_lags(&obs);
+        assert_eq!(lags[0].source, "rpc4");
+        assert_eq!(lags[0].lag_slots, 40);
+        assert!(detector.is_lagging(&lags[0]));
+        assert!(!detector.is_lagging(&lags[1]));
+    }
+
+    #[test]
+    fn test_two_lagging_of_five_is_degraded_not_forked() {
+        let detector = NetworkDetector::new(5);
+        let obs: HashMap<_, _> = [
+            create_observation(1000, "rpc0"),
+            create_observation(1000, "rpc1"),
+            create_observation(1000, "rpc2"),
+            create_observation(970, "rpc3"),
+            create_observation(970, "rpc4"),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Degraded);
+    }
+
+    #[test]
+    fn test_halted_network() {
+        let detector = NetworkDetector::new(5);
+        let mut obs = HashMap::new();
+
+        for i in 0..4 {
+            let mut observation = SlotObservation::new(12345, format!("rpc{}", i));
+            observation.timestamp = Utc::now() - chrono::Duration::seconds(10);
+            obs.insert(format!("rpc{}", i), observation);
+        }
+
+        assert_eq!(detector.detect_health(&obs), NetworkHealth::Halted);
+    }
 }
\ No newline at end of file
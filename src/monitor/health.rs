@@ -1,114 +1,296 @@
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, info, warn};
-
-use crate::types::{NetworkHealth, SlotObservation};
-use super::detector::NetworkDetector;
-
-/// Monitors network health by tracking slot progression
-pub struct HealthMonitor {
-    observations: Arc<RwLock<HashMap<String, SlotObservation>>>,
-    health: Arc<RwLock<NetworkHealth>>,
-    detector: NetworkDetector,
-    retention_seconds: u64,
-}
-
-impl HealthMonitor {
-    pub fn new(stale_threshold_secs: i64, retention_seconds: u64) -> Self {
-        Self {
-            observations: Arc::new(RwLock::new(HashMap::new())),
-            health: Arc::new(RwLock::new(NetworkHealth::Healthy)),
-            detector: NetworkDetector::new(stale_threshold_secs),
-            retention_seconds,
-        }
-    }
-
-    pub async fn record_observation(&self, obs: SlotObservation) {
-        let mut observations = self.observations.write().await;
-        
-        debug!("Recording slot {} from {}", obs.slot, obs.source);
-        observations.insert(obs.source.clone(), obs);
-
-        self.cleanup_old_observations(&mut observations);
-    }
-
-    pub async fn check_health(&self) -> NetworkHealth {
-        let observations = self.observations.read().await;
-        
-        if observations.is_empty() {
-            warn!("No slot observations available");
-            return NetworkHealth::Halted;
-        }
-
-        let health = self.detector.detect_health(&observations);
-        
-        let mut current_health = self.health.write().await;
-        if *current_health != health {
-            match health {
-                NetworkHealth::Healthy => info!("Network health: HEALTHY"),
-                NetworkHealth::Forked => warn!("Network health: FORKED"),
-                NetworkHealth::Halted => warn!("Network health: HALTED"),
-            }
-        }
-        *current_health = health;
-        
-        health
-    }
-
-    pub async fn get_health(&self) -> NetworkHealth {
-        *self.health.read().await
-    }
-
-    pub async fn get_observations(&self) -> HashMap<String, SlotObservation> {
-        self.observations.read().await.clone()
-    }
-
-    fn cleanup_old_observations(&self, observations: &mut HashMap<String, SlotObservation>) {
-        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(self.retention_seconds as i64);
-        observations.retain(|_, obs| obs.timestamp > cutoff);
-    }
-}
-
-impl Default for HealthMonitor {
-    fn default() -> Self {
-        Self::new(5, 30)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[tokio::test]
-    async fn test_record_and_check_health() {
-        let monitor = HealthMonitor::default();
-        
-        for i in 0..4 {
-            monitor.record_observation(
-                SlotObservation::new(12345, format!("rpc{}", i))
-            ).await;
-        }
-
-        let health = monitor.check_health().await;
-        assert_eq!(health, NetworkHealth::Healthy);
-    }
-
-    #[tokio::test]
-    async fn test_observation_cleanup() {
-        let monitor = HealthMonitor::new(5, 1);
-        
-        monitor.record_observation(
-            SlotObservation::new(12345, "rpc1")
-        ).await;
-
-        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
-
-        monitor.record_observation(
-            SlotObservation::new(12346, "rpc2")
-        ).await;
-
-        let obs = monitor.get_observations().await;
-        assert_eq!(obs.len(), 1);
-    }
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::types::{ForkReport, NetworkHealth, SlotObservation, SourceLag};
+use super::detector::NetworkDetector;
+
+// Per-source slot history used to measure slot production
+const SLOT_HISTORY_SECS: i64 = 20;
+// Shortest history span that gives a meaningful rate
+const MIN_RATE_SPAN_SECS: f64 = 4.0;
+
+/// Recent (timestamp, slot) samples from one source, oldest first
+type SlotHistory = VecDeque<(DateTime<Utc>, u64)>;
+
+/// Monitors network health by tracking slot progression
+pub struct HealthMonitor {
+    observations: Arc<RwLock<HashMap<String, SlotObservation>>>,
+    slot_history: Arc<RwLock<HashMap<String, SlotHistory>>>,
+    health: Arc<RwLock<NetworkHealth>>,
+    fork_report: Arc<RwLock<Option<ForkReport>>>,
+    source_lags: Arc<RwLock<Vec<SourceLag>>>,
+    detector: NetworkDetector,
+    retention_seconds: u64,
+}
+
+impl HealthMonitor {
+    pub fn new(stale_threshold_secs: i64, retention_seconds: u64) -> Self {
+        Self {
+            observations: Arc::new(RwLock::new(HashMap::new())),
+            slot_history: Arc::new(RwLock::new(HashMap::new())),
+            health: Arc::new(RwLock::new(NetworkHealth::Healthy)),
+            fork_report: Arc::new(RwLock::new(None)),
+            source_lags: Arc::new(RwLock::new(Vec::new())),
+            detector: NetworkDetector::new(stale_threshold_secs),
+            retention_seconds,
+        }
+    }
+
+    pub async fn record_observation(&self, obs: SlotObservation) {
+        let mut observations = self.observations.write().await;
+        
+        debug!("Recording slot {} from {}", obs.slot, obs.source);
+        self.record_slot_history(&obs).await;
+        observations.insert(obs.source.clone(), obs);
+
+        self.cleanup_old_observations(&mut observations);
+    }
+
+    async fn record_slot_history(&self, obs: &SlotObservation) {
+        let mut history = self.slot_history.write().await;
+        let samples = history.entry(obs.source.clone()).or_default();
+
+        samples.push_back((obs.timestamp, obs.slot));
+
+        let cutoff = obs.timestamp - chrono::Duration::seconds(SLOT_HISTORY_SECS);
+        while samples.front().is_some_and(|(ts, _)| *ts < cutoff) {
+            samples.pop_front();
+        }
+    }
+
+    /// Slot production rate (slots/sec) per source over the recent history
+    ///
+    /// Sources with less than a few seconds of history are omitted.
+    pub async fn get_slot_rates(&self) -> HashMap<String, f64> {
+        let history = self.slot_history.read().await;
+
+        history
+            .iter()
+            .filter_map(|(source, samples)| {
+                let (first_ts, first_slot) = samples.front()?;
+                let (last_ts, last_slot) = samples.back()?;
+
+                let span_secs = (*last_ts - *first_ts).num_milliseconds() as f64 / 1000.0;
+                if span_secs < MIN_RATE_SPAN_SECS {
+                    return None;
+                }
+
+                let produced = last_slot.saturating_sub(*first_slot) as f64;
+                Some((source.clone(), produced / span_secs))
+            })
+            .collect()
+    }
+
+    pub async fn check_health(&self) -> NetworkHealth {
+        let observations = self.observations.read().await;
+        
+        if observations.is_empty() {
+            warn!("No slot observations available");
+            return NetworkHealth::Halted;
+        }
+
+        let slot_rates = self.get_slot_rates().await;
+        let health = self
+            .detector
+            .detect_health(&observations)
+            .worst(self.detector.detect_progression(&observations, &slot_rates));
+        self.update_fork_report(&observations).await;
+        *self.source_lags.write().await = self.detector.source_lags(&observations);
+        
+        let mut current_health = self.health.write().await;
+        if *current_health != health {
+            match health {
+                NetworkHealth::Healthy => info!("Network health: HEALTHY"),
+                NetworkHealth::Degraded => warn!("Network health: DEGRADED"),
+                NetworkHealth::Slow => warn!("Network health: SLOW"),
+                NetworkHealth::Forked => warn!("Network health: FORKED"),
+                NetworkHealth::Halted => warn!("Network health: HALTED"),
+            }
+        }
+        *current_health = health;
+        
+        health
+    }
+
+    pub async fn get_health(&self) -> NetworkHealth {
+        *self.health.read().await
+    }
+
+    /// Branches seen in the latest health check, if sources disagree on a block
+    pub async fn get_fork_report(&self) -> Option<ForkReport> {
+        self.fork_report.read().await.clone()
+    }
+
+    /// Per-source lag behind the median slot from the latest health check
+    pub async fn get_source_lags(&self) -> Vec<SourceLag> {
+        self.source_lags.read().await.clone()
+    }
+
+    /// Sources currently lagging far enough to degrade the network
+    pub async fn get_lagging_sources(&self) -> Vec<SourceLag> {
+        self.get_source_lags()
+            .await
+            .into_iter()
+            .filter(|lag| self.detector.is_lagging(lag))
+            .collect()
+    }
+
+    async fn update_fork_report(&self, observations: &HashMap<String, SlotObservation>) {
+        let report = self.detector.detect_fork(observations);
+
+        let mut current_report = self.fork_report.write().await;
+        if let Some(report) = &report {
+            let is_new = current_report
+                .as_ref()
+                .is_none_or(|current| current.slot != report.slot);
+            if is_new {
+                warn!("Conflicting blocks at slot {}", report.slot);
+                for branch in &report.branches {
+                    warn!(
+                        "  branch {} (parent {:?}): {:?}",
+                        branch.blockhash, branch.parent_slot, branch.sources
+                    );
+                }
+            }
+        }
+        *current_report = report;
+    }
+
+    /// Highest slot any source has reported within the retention window
+    pub async fn get_current_slot(&self) -> Option<u64> {
+        self.observations.read().await.values().map(|obs| obs.slot).max()
+    }
+
+    pub async fn get_observations(&self) -> HashMap<String, SlotObservation> {
+        self.observations.read().await.clone()
+    }
+
+    fn cleanup_old_observations(&self, observations: &mut HashMap<String, SlotObservation>) {
+        let cutoff = chrono::Utc::now() - chrono::Duration::seconds(self.retention_seconds as i64);
+        observations.retain(|_, obs| obs.timestamp > cutoff);
+    }
+}
+
+impl Default for HealthMonitor {
+    fn default() -> Self {
+        Self::new(5, 30)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_and_check_health() {
+        let monitor = HealthMonitor::default();
+        
+        for i in 0..4 {
+            monitor.record_observation(
+                SlotObservation::new(12345, format!("rpc{}", i))
+            ).await;
+        }
+
+        let health = monitor.check_health().await;
+        assert_eq!(health, NetworkHealth::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_fork_report_tracks_branches() {
+        let monitor = HealthMonitor::default();
+
+        monitor.record_observation(SlotObservation::new(500, "rpc0").with_block(500, "hashA", 499)).await;
+        monitor.record_observation(SlotObservation::new(500, "rpc1").with_block(500, "hashB", 499)).await;
+
+        assert_eq!(monitor.check_health().await, NetworkHealth::Forked);
+        let report = monitor.get_fork_report().await.unwrap();
+        assert_eq!(report.branches.len(), 2);
+
+        monitor.record_observation(SlotObservation::new(501, "rpc1").with_block(501, "hashC", 500)).await;
+        monitor.check_health().await;
+        assert!(monitor.get_fork_report().await.is_none());
+    }
+
+    fn observed_at(slot: u64, source: &str, secs_ago: i64) -> SlotObservation {
+        let mut obs = SlotObservation::new(slot, source);
+        obs.timestamp = Utc::now() - chrono::Duration::seconds(secs_ago);
+        obs
+    }
+
+    #[tokio::test]
+    async fn test_frozen_slot_is_halted() {
+        let monitor = HealthMonitor::default();
+
+        for source in ["rpc0", "rpc1"] {
+            monitor.record_observation(observed_at(12345, source, 10)).await;
+            monitor.record_observation(observed_at(12345, source, 0)).await;
+        }
+
+        assert_eq!(monitor.get_slot_rates().await.get("rpc0"), Some(&0.0));
+        assert_eq!(monitor.check_health().await, NetworkHealth::Halted);
+    }
+
+    #[tokio::test]
+    async fn test_slow_slot_production() {
+        let monitor = HealthMonitor::default();
+
+        for source in ["rpc0", "rpc1"] {
+            monitor.record_observation(observed_at(1000, source, 10)).await;
+            monitor.record_observation(observed_at(1005, source, 0)).await;
+        }
+
+        assert_eq!(monitor.check_health().await, NetworkHealth::Slow);
+    }
+
+    #[tokio::test]
+    async fn test_normal_slot_production() {
+        let monitor = HealthMonitor::default();
+
+        for source in ["rpc0", "rpc1"] {
+            monitor.record_observation(observed_at(1000, source, 10)).await;
+            monitor.record_observation(observed_at(1025, source, 0)).await;
+        }
+
+        let rates = monitor.get_slot_rates().await;
+        assert!((rates["rpc0"] - 2.5).abs() < 0.1);
+        assert_eq!(monitor.check_health().await, NetworkHealth::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_lagging_source_details() {
+        let monitor = HealthMonitor::default();
+
+        for (slot, source) in [(1000, "rpc0"), (1001, "rpc1"), (1000, "rpc2"), (950, "rpc3")] {
+            monitor.record_observation(SlotObservation::new(slot, source)).await;
+        }
+
+        assert_eq!(monitor.check_health().await, NetworkHealth::Degraded);
+
+        let lagging = monitor.get_lagging_sources().await;
+        assert_eq!(lagging.len(), 1);
+        assert_eq!(lagging[0].source, "rpc3");
+        assert_eq!(lagging[0].lag_slots, 50);
+        assert_eq!(monitor.get_source_lags().await.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_observation_cleanup() {
+        let monitor = HealthMonitor::new(5, 1);
+        
+        monitor.record_observation(
+            SlotObservation::new(12345, "rpc1")
+        ).await;
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+
+        monitor.record_observation(
+            SlotObservation::new(12346, "rpc2")
+        ).await;
+
+        let obs = monitor.get_observations().await;
+        assert_eq!(obs.len(), 1);
+    }
 }
\ No newline at end of file
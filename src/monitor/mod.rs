@@ -1,5 +1,7 @@
-mod detector;
-mod health;
-
-pub use detector::NetworkDetector;
-pub use health::HealthMonitor;
+mod detector;
+mod health;
+mod stake;
+
+pub use detector::NetworkDetector;
+pub use health::HealthMonitor;
+pub use stake::{StakeMonitor, VoteSnapshot};
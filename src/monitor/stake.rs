@@ -0,0 +1,153 @@
+use chrono::{DateTime, Utc};
+use solana_client::rpc_response::RpcVoteAccountStatus;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tracing::debug;
+
+// Older vote snapshots say little about recent slots
+const SNAPSHOT_MAX_AGE_SECS: i64 = 60;
+
+/// Stake and latest vote of every vote account at one point in time
+#[derive(Debug, Clone)]
+pub struct VoteSnapshot {
+    /// (activated stake, last voted slot) per vote account
+    pub votes: Vec<(u64, u64)>,
+    /// Active stake across current and delinquent vote accounts
+    pub total_stake: u64,
+    pub fetched_at: DateTime<Utc>,
+}
+
+impl VoteSnapshot {
+    pub fn from_vote_accounts(status: &RpcVoteAccountStatus) -> Self {
+        let votes: Vec<(u64, u64)> = status
+            .current
+            .iter()
+            .chain(status.delinquent.iter())
+            .map(|account| (account.activated_stake, account.last_vote))
+            .collect();
+
+        Self {
+            total_stake: votes.iter().map(|(stake, _)| stake).sum(),
+            votes,
+            fetched_at: Utc::now(),
+        }
+    }
+
+    /// Percent of active stake whose latest vote is on or past `slot`
+    ///
+    /// Votes are cast in slot order, so a validator that voted past `slot`
+    /// has already voted on the chain containing it (or skipped it).
+    pub fn stake_percent_at(&self, slot: u64) -> f64 {
+        if self.total_stake == 0 {
+            return 0.0;
+        }
+
+        let voted: u64 = self
+            .votes
+            .iter()
+            .filter(|(_, last_vote)| *last_vote >= slot)
+            .map(|(stake, _)| stake)
+            .sum();
+
+        voted as f64 / self.total_stake as f64 * 100.0
+    }
+
+    pub fn is_stale(&self) -> bool {
+        (Utc::now() - self.fetched_at).num_seconds() > SNAPSHOT_MAX_AGE_SECS
+    }
+}
+
+/// Tracks the vote account set to measure stake confirmation of slots
+pub struct StakeMonitor {
+    snapshot: Arc<RwLock<Option<VoteSnapshot>>>,
+}
+
+impl StakeMonitor {
+    pub fn new() -> Self {
+        Self {
+            snapshot: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub async fn update(&self, status: &RpcVoteAccountStatus) {
+        let snapshot = VoteSnapshot::from_vote_accounts(status);
+
+        debug!(
+            "Vote snapshot: {} accounts, {} lamports active stake",
+            snapshot.votes.len(),
+            snapshot.total_stake
+        );
+
+        *self.snapshot.write().await = Some(snapshot);
+    }
+
+    /// Stake percent confirming `slot`, or None without a fresh snapshot
+    pub async fn stake_percent_at(&self, slot: u64) -> Option<f64> {
+        self.snapshot
+            .read()
+            .await
+            .as_ref()
+            .filter(|snapshot| !snapshot.is_stale())
+            .map(|snapshot| snapshot.stake_percent_at(slot))
+    }
+
+    pub async fn get_snapshot(&self) -> Option<VoteSnapshot> {
+        self.snapshot.read().await.clone()
+    }
+}
+
+impl Default for StakeMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::rpc_response::RpcVoteAccountInfo;
+
+    fn vote_account(activated_stake: u64, last_vote: u64) -> RpcVoteAccountInfo {
+        RpcVoteAccountInfo {
+            vote_pubkey: String::new(),
+            node_pubkey: String::new(),
+            activated_stake,
+            commission: 0,
+            epoch_vote_account: true,
+            epoch_credits: vec![],
+            last_vote,
+            root_slot: last_vote.saturating_sub(32),
+        }
+    }
+
+    fn status() -> RpcVoteAccountStatus {
+        RpcVoteAccountStatus {
+            current: vec![
+                vote_account(50, 1010),
+                vote_account(30, 1005),
+                vote_account(15, 1000),
+            ],
+            delinquent: vec![vote_account(5, 900)],
+        }
+    }
+
+    #[test]
+    fn test_stake_percent_at_slot() {
+        let snapshot = VoteSnapshot::from_vote_accounts(&status());
+
+        assert_eq!(snapshot.total_stake, 100);
+        assert_eq!(snapshot.stake_percent_at(900), 100.0);
+        assert_eq!(snapshot.stake_percent_at(1000), 95.0);
+        assert_eq!(snapshot.stake_percent_at(1005), 80.0);
+        assert_eq!(snapshot.stake_percent_at(1011), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_stake_monitor_requires_snapshot() {
+        let monitor = StakeMonitor::new();
+        assert_eq!(monitor.stake_percent_at(1000).await, None);
+
+        monitor.update(&status()).await;
+        assert_eq!(monitor.stake_percent_at(1000).await, Some(95.0));
+    }
+}
@@ -1,320 +1,557 @@
-use serde::{Deserialize, Serialize};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum BridgeType {
-    Wormhole,
-    Across,
-    DeBridge,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(tag = "instruction", rename_all = "snake_case")]
-pub enum BridgeInstruction {
-    /// Transfer wrapped tokens from Solana to another chain
-    TransferWrapped {
-        amount: u64,
-        target_chain: u16,
-        #[serde(serialize_with = "serialize_hex")]
-        recipient: Vec<u8>,
-    },
-    
-    /// Transfer native tokens from Solana to another chain
-    TransferNative {
-        amount: u64,
-        target_chain: u16,
-        #[serde(serialize_with = "serialize_hex")]
-        recipient: Vec<u8>,
-    },
-    
-    /// Transfer with additional payload data
-    TransferWithPayload {
-        amount: u64,
-        target_chain: u16,
-    },
-    
-    /// Attest a token for bridging (registers new token)
-    AttestToken,
-    
-    /// Complete a transfer by receiving tokens on Solana
-    CompleteTransfer {
-        #[serde(serialize_with = "serialize_hex")]
-        vaa_hash: Vec<u8>,
-        is_native: bool,
-    },
-    
-    /// Complete a transfer with payload
-    CompleteTransferWithPayload,
-    
-    /// Wrapped token creation or completion operations
-    WrappedTokenOperation {
-        operation_type: String,
-    },
-    
-    /// Unknown or unsupported instruction
-    Unknown,
-}
-
-// Serialize bytes as hex string
-fn serialize_hex<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: serde::Serializer,
-{
-    if bytes.is_empty() {
-        serializer.serialize_str("0x")
-    } else {
-        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
-    }
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ParsedTransaction {
-    pub bridge_type: BridgeType,
-    pub instruction: BridgeInstruction,
-}
-
-impl ParsedTransaction {
-    pub fn new(bridge_type: BridgeType, instruction: BridgeInstruction) -> Self {
-        Self {
-            bridge_type,
-            instruction,
-        }
-    }
-    
-    pub fn bridge_name(&self) -> &str {
-        match self.bridge_type {
-            BridgeType::Wormhole => "Wormhole",
-            BridgeType::Across => "Across Protocol",
-            BridgeType::DeBridge => "DeBridge",
-        }
-    }
-
-    pub fn instruction_name(&self) -> &str {
-        match &self.instruction {
-            BridgeInstruction::TransferWrapped { .. } => "TransferWrapped",
-            BridgeInstruction::TransferNative { .. } => "TransferNative",
-            BridgeInstruction::TransferWithPayload { .. } => "TransferWithPayload",
-            BridgeInstruction::AttestToken => "AttestToken",
-            BridgeInstruction::CompleteTransfer { .. } => "CompleteTransfer",
-            BridgeInstruction::CompleteTransferWithPayload => "CompleteTransferWithPayload",
-            BridgeInstruction::WrappedTokenOperation { operation_type } => operation_type,
-            BridgeInstruction::Unknown => "Unknown",
-        }
-    }
-
-    pub fn amount(&self) -> Option<u64> {
-        match &self.instruction {
-            BridgeInstruction::TransferWrapped { amount, .. } => Some(*amount),
-            BridgeInstruction::TransferNative { amount, .. } => Some(*amount),
-            BridgeInstruction::TransferWithPayload { amount, .. } => Some(*amount),
-            _ => None,
-        }
-    }
-
-    pub fn target_chain(&self) -> Option<u16> {
-        match &self.instruction {
-            BridgeInstruction::TransferWrapped { target_chain, .. } => Some(*target_chain),
-            BridgeInstruction::TransferNative { target_chain, .. } => Some(*target_chain),
-            BridgeInstruction::TransferWithPayload { target_chain, .. } => Some(*target_chain),
-            _ => None,
-        }
-    }
-    
-    pub fn target_chain_name(&self) -> Option<&str> {
-        self.target_chain().map(|chain_id| {
-            match chain_id {
-                1 => "Solana",
-                2 => "Ethereum",
-                3 => "Terra",
-                4 => "BSC",
-                5 => "Polygon",
-                6 => "Avalanche",
-                7 => "Oasis",
-                8 => "Algorand",
-                9 => "Aurora",
-                10 => "Fantom",
-                11 => "Karura",
-                12 => "Acala",
-                13 => "Klaytn",
-                14 => "Celo",
-                15 => "Near",
-                16 => "Moonbeam",
-                17 => "Neon",
-                18 => "Terra2",
-                19 => "Injective",
-                20 => "Osmosis",
-                21 => "Sui",
-                22 => "Aptos",
-                23 => "Arbitrum",
-                24 => "Optimism",
-                25 => "Gnosis",
-                26 => "Pythnet",
-                28 => "Xpla",
-                29 => "BTC",
-                30 => "Base",
-                32 => "Sei",
-                33 => "Rootstock",
-                34 => "Scroll",
-                35 => "Mantle",
-                36 => "Blast",
-                37 => "Xlayer",
-                38 => "Linea",
-                39 => "Berachain",
-                40 => "Seievm",
-                41 => "Cosmoshub",
-                42 => "Evmos",
-                43 => "Kujira",
-                44 => "Neutron",
-                45 => "Celestia",
-                46 => "Stargaze",
-                47 => "Seda",
-                48 => "Dymension",
-                49 => "Provenance",
-                50 => "Sepolia",
-                4000 => "PolygonSepolia",
-                10002 => "BaseSepolia",
-                10003 => "OptimismSepolia",
-                10004 => "HoleskyTestnet",
-                10005 => "ArbitrumSepolia",
-                _ => "Unknown",
-            }
-        })
-    }
-
-    pub fn recipient(&self) -> Option<&[u8]> {
-        match &self.instruction {
-            BridgeInstruction::TransferWrapped { recipient, .. } => Some(recipient),
-            BridgeInstruction::TransferNative { recipient, .. } => Some(recipient),
-            _ => None,
-        }
-    }
-
-    pub fn vaa_hash(&self) -> Option<&[u8]> {
-        match &self.instruction {
-            BridgeInstruction::CompleteTransfer { vaa_hash, .. } => Some(vaa_hash),
-            _ => None,
-        }
-    }
-
-    pub fn is_outbound(&self) -> bool {
-        matches!(
-            &self.instruction,
-            BridgeInstruction::TransferWrapped { .. }
-                | BridgeInstruction::TransferNative { .. }
-                | BridgeInstruction::TransferWithPayload { .. }
-        )
-    }
-
-    pub fn is_inbound(&self) -> bool {
-        matches!(
-            &self.instruction,
-            BridgeInstruction::CompleteTransfer { .. }
-                | BridgeInstruction::CompleteTransferWithPayload
-        )
-    }
-
-    pub fn is_token_operation(&self) -> bool {
-        matches!(
-            &self.instruction,
-            BridgeInstruction::AttestToken
-                | BridgeInstruction::WrappedTokenOperation { .. }
-        )
-    }
-
-    pub fn direction(&self) -> &str {
-        if self.is_outbound() {
-            "Outbound"
-        } else if self.is_inbound() {
-            "Inbound"
-        } else if self.is_token_operation() {
-            "Token Operation"
-        } else {
-            "Unknown"
-        }
-    }
-}
-
-impl std::fmt::Display for ParsedTransaction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} - {}",
-            self.bridge_name(),
-            self.instruction_name()
-        )?;
-        
-        if let Some(amount) = self.amount() {
-            write!(f, " ({} lamports)", amount)?;
-        }
-        
-        if let Some(chain) = self.target_chain_name() {
-            write!(f, " → {}", chain)?;
-        }
-        
-        Ok(())
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_transfer_wrapped() {
-        let instruction = BridgeInstruction::TransferWrapped {
-            amount: 1_000_000,
-            target_chain: 2,
-            recipient: vec![0x12, 0x34],
-        };
-        
-        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
-        
-        assert_eq!(tx.instruction_name(), "TransferWrapped");
-        assert_eq!(tx.amount(), Some(1_000_000));
-        assert_eq!(tx.target_chain(), Some(2));
-        assert_eq!(tx.target_chain_name(), Some("Ethereum"));
-        assert!(tx.is_outbound());
-        assert!(!tx.is_inbound());
-    }
-
-    #[test]
-    fn test_complete_transfer() {
-        let instruction = BridgeInstruction::CompleteTransfer {
-            vaa_hash: vec![],
-            is_native: false,
-        };
-        
-        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
-        
-        assert_eq!(tx.instruction_name(), "CompleteTransfer");
-        assert_eq!(tx.amount(), None);
-        assert!(!tx.is_outbound());
-        assert!(tx.is_inbound());
-    }
-
-    #[test]
-    fn test_attest_token() {
-        let instruction = BridgeInstruction::AttestToken;
-        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
-        
-        assert_eq!(tx.instruction_name(), "AttestToken");
-        assert!(tx.is_token_operation());
-        assert_eq!(tx.direction(), "Token Operation");
-    }
-
-    #[test]
-    fn test_display() {
-        let instruction = BridgeInstruction::TransferWrapped {
-            amount: 1_000_000,
-            target_chain: 30,
-            recipient: vec![],
-        };
-        
-        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
-        let display = format!("{}", tx);
-        
-        assert!(display.contains("Wormhole"));
-        assert!(display.contains("TransferWrapped"));
-        assert!(display.contains("1000000"));
-        assert!(display.contains("Base"));
-    }
-}
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+
+/// Bridge protocol of a transaction
+///
+/// Serialized as its name, so bridges registered outside this crate
+/// round-trip through `Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum BridgeType {
+    Wormhole,
+    /// A bridge without a built-in variant, named by its parser
+    Other(String),
+}
+
+impl BridgeType {
+    pub fn as_str(&self) -> &str {
+        match self {
+            BridgeType::Wormhole => "Wormhole",
+            BridgeType::Other(name) => name,
+        }
+    }
+}
+
+impl From<String> for BridgeType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "Wormhole" => BridgeType::Wormhole,
+            _ => BridgeType::Other(name),
+        }
+    }
+}
+
+impl From<BridgeType> for String {
+    fn from(bridge_type: BridgeType) -> Self {
+        bridge_type.as_str().to_string()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "instruction", rename_all = "snake_case")]
+pub enum BridgeInstruction {
+    /// Transfer wrapped tokens from Solana to another chain
+    TransferWrapped {
+        amount: u64,
+        target_chain: u16,
+        #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+        recipient: Vec<u8>,
+    },
+    
+    /// Transfer native tokens from Solana to another chain
+    TransferNative {
+        amount: u64,
+        target_chain: u16,
+        #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+        recipient: Vec<u8>,
+    },
+    
+    /// Transfer with additional payload data
+    TransferWithPayload {
+        amount: u64,
+        target_chain: u16,
+    },
+    
+    /// Attest a token for bridging (registers new token)
+    AttestToken,
+    
+    /// Complete a transfer by receiving tokens on Solana
+    CompleteTransfer {
+        #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+        vaa_hash: Vec<u8>,
+        is_native: bool,
+    },
+    
+    /// Complete a transfer with payload
+    CompleteTransferWithPayload,
+    
+    /// Wrapped token creation or completion operations
+    WrappedTokenOperation {
+        operation_type: String,
+    },
+
+    /// Instruction of a bridge without built-in decoding
+    ///
+    /// Counts as an outbound transfer when it names a target chain.
+    Custom {
+        name: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        amount: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        target_chain: Option<u16>,
+    },
+    
+    /// Unknown or unsupported instruction
+    Unknown,
+}
+
+// Serialize bytes as hex string
+fn serialize_hex<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    if bytes.is_empty() {
+        serializer.serialize_str("0x")
+    } else {
+        serializer.serialize_str(&format!("0x{}", hex::encode(bytes)))
+    }
+}
+
+fn deserialize_hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let hex_str = String::deserialize(deserializer)?;
+    hex::decode(hex_str.trim_start_matches("0x")).map_err(serde::de::Error::custom)
+}
+
+/// Where a bridge instruction sits in its transaction
+///
+/// Ordered by execution: a direct call runs before the CPIs it makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct InstructionLocation {
+    /// Index of the top-level instruction that invoked the bridge
+    pub outer_index: usize,
+    /// Position among that instruction's CPIs; None when the bridge was called directly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_index: Option<usize>,
+}
+
+/// A decoded bridge instruction and where it ran
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BridgeCall {
+    pub location: InstructionLocation,
+    pub instruction: BridgeInstruction,
+    /// Token mint the call moves, read from its accounts by the bridge parser
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+}
+
+impl BridgeCall {
+    pub fn new(location: InstructionLocation, instruction: BridgeInstruction) -> Self {
+        Self {
+            location,
+            instruction,
+            mint: None,
+        }
+    }
+
+    pub fn with_mint(mut self, mint: Option<String>) -> Self {
+        self.mint = mint;
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        match &self.instruction {
+            BridgeInstruction::TransferWrapped { .. } => "TransferWrapped",
+            BridgeInstruction::TransferNative { .. } => "TransferNative",
+            BridgeInstruction::TransferWithPayload { .. } => "TransferWithPayload",
+            BridgeInstruction::AttestToken => "AttestToken",
+            BridgeInstruction::CompleteTransfer { .. } => "CompleteTransfer",
+            BridgeInstruction::CompleteTransferWithPayload => "CompleteTransferWithPayload",
+            BridgeInstruction::WrappedTokenOperation { operation_type } => operation_type,
+            BridgeInstruction::Custom { name, .. } => name,
+            BridgeInstruction::Unknown => "Unknown",
+        }
+    }
+
+    pub fn amount(&self) -> Option<u64> {
+        match &self.instruction {
+            BridgeInstruction::TransferWrapped { amount, .. } => Some(*amount),
+            BridgeInstruction::TransferNative { amount, .. } => Some(*amount),
+            BridgeInstruction::TransferWithPayload { amount, .. } => Some(*amount),
+            BridgeInstruction::Custom { amount, .. } => *amount,
+            _ => None,
+        }
+    }
+
+    pub fn target_chain(&self) -> Option<u16> {
+        match &self.instruction {
+            BridgeInstruction::TransferWrapped { target_chain, .. } => Some(*target_chain),
+            BridgeInstruction::TransferNative { target_chain, .. } => Some(*target_chain),
+            BridgeInstruction::TransferWithPayload { target_chain, .. } => Some(*target_chain),
+            BridgeInstruction::Custom { target_chain, .. } => *target_chain,
+            _ => None,
+        }
+    }
+
+    pub fn is_outbound(&self) -> bool {
+        matches!(
+            &self.instruction,
+            BridgeInstruction::TransferWrapped { .. }
+                | BridgeInstruction::TransferNative { .. }
+                | BridgeInstruction::TransferWithPayload { .. }
+                | BridgeInstruction::Custom { target_chain: Some(_), .. }
+        )
+    }
+
+    pub fn is_inbound(&self) -> bool {
+        matches!(
+            &self.instruction,
+            BridgeInstruction::CompleteTransfer { .. }
+                | BridgeInstruction::CompleteTransferWithPayload
+        )
+    }
+
+    pub fn is_token_operation(&self) -> bool {
+        matches!(
+            &self.instruction,
+            BridgeInstruction::AttestToken
+                | BridgeInstruction::WrappedTokenOperation { .. }
+        )
+    }
+}
+
+/// A bridge transaction and every bridge instruction it ran, in execution order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParsedTransaction {
+    /// Bridge of the first registered parser that detected the transaction
+    pub bridge_type: BridgeType,
+    /// Empty when the bridge was invoked but none of its instructions decoded
+    pub instructions: Vec<BridgeCall>,
+    /// Token mint moved by the transaction, when it can be told apart
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+}
+
+impl ParsedTransaction {
+    /// A transaction whose first top-level instruction is a direct bridge call
+    pub fn new(bridge_type: BridgeType, instruction: BridgeInstruction) -> Self {
+        let location = InstructionLocation {
+            outer_index: 0,
+            inner_index: None,
+        };
+        Self::from_calls(bridge_type, vec![BridgeCall::new(location, instruction)])
+    }
+
+    pub fn from_calls(bridge_type: BridgeType, instructions: Vec<BridgeCall>) -> Self {
+        Self {
+            bridge_type,
+            instructions,
+            mint: None,
+        }
+    }
+
+    pub fn with_mint(mut self, mint: Option<String>) -> Self {
+        self.mint = mint;
+        self
+    }
+
+    /// Mint named by every call that carries an amount, if they all name the same one
+    pub fn call_mint(&self) -> Option<&str> {
+        let mut mints = self
+            .instructions
+            .iter()
+            .filter(|call| call.amount().is_some())
+            .map(|call| call.mint.as_deref());
+
+        let mint = mints.next()??;
+        mints.all(|other| other == Some(mint)).then_some(mint)
+    }
+    
+    pub fn bridge_name(&self) -> &str {
+        match &self.bridge_type {
+            BridgeType::Wormhole => "Wormhole",
+            BridgeType::Other(name) => name,
+        }
+    }
+
+    /// Name of the first bridge instruction
+    pub fn instruction_name(&self) -> &str {
+        self.instructions.first().map_or("Unknown", BridgeCall::name)
+    }
+
+    /// Total amount sent by the transaction's outbound transfers
+    ///
+    /// None when they move different mints, whose raw units don't add up.
+    pub fn amount(&self) -> Option<u64> {
+        match self.amounts_by_mint().as_slice() {
+            [(_, amount)] => Some(*amount),
+            _ => None,
+        }
+    }
+
+    /// Amount moved per mint, in call order
+    ///
+    /// Calls that name no mint count under the transaction's `mint`.
+    pub fn amounts_by_mint(&self) -> Vec<(Option<&str>, u64)> {
+        let mut totals: Vec<(Option<&str>, u64)> = Vec::new();
+        for call in &self.instructions {
+            let Some(amount) = call.amount() else {
+                continue;
+            };
+            let mint = call.mint.as_deref().or(self.mint.as_deref());
+            match totals.iter_mut().find(|(other, _)| *other == mint) {
+                Some((_, total)) => *total = total.saturating_add(amount),
+                None => totals.push((mint, amount)),
+            }
+        }
+        totals
+    }
+
+    /// Target chain of the outbound transfers, if they all share one
+    pub fn target_chain(&self) -> Option<u16> {
+        match self.target_chains().as_slice() {
+            [chain] => Some(*chain),
+            _ => None,
+        }
+    }
+
+    /// Distinct target chains of the outbound transfers, in order
+    pub fn target_chains(&self) -> Vec<u16> {
+        let mut chains = Vec::new();
+        for chain in self.instructions.iter().filter_map(BridgeCall::target_chain) {
+            if !chains.contains(&chain) {
+                chains.push(chain);
+            }
+        }
+        chains
+    }
+    
+    pub fn target_chain_name(&self) -> Option<&str> {
+        self.target_chain().map(|chain_id| {
+            match chain_id {
+                1 => "Solana",
+                2 => "Ethereum",
+                3 => "Terra",
+                4 => "BSC",
+                5 => "Polygon",
+                6 => "Avalanche",
+                7 => "Oasis",
+                8 => "Algorand",
+                9 => "Aurora",
+                10 => "Fantom",
+                11 => "Karura",
+                12 => "Acala",
+                13 => "Klaytn",
+                14 => "Celo",
+                15 => "Near",
+                16 => "Moonbeam",
+                17 => "Neon",
+                18 => "Terra2",
+                19 => "Injective",
+                20 => "Osmosis",
+                21 => "Sui",
+                22 => "Aptos",
+                23 => "Arbitrum",
+                24 => "Optimism",
+                25 => "Gnosis",
+                26 => "Pythnet",
+                28 => "Xpla",
+                29 => "BTC",
+                30 => "Base",
+                32 => "Sei",
+                33 => "Rootstock",
+                34 => "Scroll",
+                35 => "Mantle",
+                36 => "Blast",
+                37 => "Xlayer",
+                38 => "Linea",
+                39 => "Berachain",
+                40 => "Seievm",
+                41 => "Cosmoshub",
+                42 => "Evmos",
+                43 => "Kujira",
+                44 => "Neutron",
+                45 => "Celestia",
+                46 => "Stargaze",
+                47 => "Seda",
+                48 => "Dymension",
+                49 => "Provenance",
+                50 => "Sepolia",
+                4000 => "PolygonSepolia",
+                10002 => "BaseSepolia",
+                10003 => "OptimismSepolia",
+                10004 => "HoleskyTestnet",
+                10005 => "ArbitrumSepolia",
+                _ => "Unknown",
+            }
+        })
+    }
+
+    /// Recipient of the first outbound transfer that names one
+    pub fn recipient(&self) -> Option<&[u8]> {
+        self.instructions.iter().find_map(|call| match &call.instruction {
+            BridgeInstruction::TransferWrapped { recipient, .. } => Some(recipient.as_slice()),
+            BridgeInstruction::TransferNative { recipient, .. } => Some(recipient.as_slice()),
+            _ => None,
+        })
+    }
+
+    /// VAA hash of the first completed transfer
+    pub fn vaa_hash(&self) -> Option<&[u8]> {
+        self.instructions.iter().find_map(|call| match &call.instruction {
+            BridgeInstruction::CompleteTransfer { vaa_hash, .. } => Some(vaa_hash.as_slice()),
+            _ => None,
+        })
+    }
+
+    pub fn is_outbound(&self) -> bool {
+        self.instructions.iter().any(BridgeCall::is_outbound)
+    }
+
+    pub fn is_inbound(&self) -> bool {
+        self.instructions.iter().any(BridgeCall::is_inbound)
+    }
+
+    pub fn is_token_operation(&self) -> bool {
+        self.instructions.iter().any(BridgeCall::is_token_operation)
+    }
+
+    /// Value-moving instructions decide the direction; outbound wins over inbound
+    pub fn direction(&self) -> &str {
+        if self.is_outbound() {
+            "Outbound"
+        } else if self.is_inbound() {
+            "Inbound"
+        } else if self.is_token_operation() {
+            "Token Operation"
+        } else {
+            "Unknown"
+        }
+    }
+}
+
+impl std::fmt::Display for ParsedTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let names: Vec<&str> = self.instructions.iter().map(BridgeCall::name).collect();
+        write!(
+            f,
+            "{} - {}",
+            self.bridge_name(),
+            if names.is_empty() { "Unknown".to_string() } else { names.join(", ") }
+        )?;
+        
+        if let Some(amount) = self.amount() {
+            write!(f, " ({} lamports)", amount)?;
+        }
+        
+        if let Some(chain) = self.target_chain_name() {
+            write!(f, " → {}", chain)?;
+        }
+        
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_wrapped() {
+        let instruction = BridgeInstruction::TransferWrapped {
+            amount: 1_000_000,
+            target_chain: 2,
+            recipient: vec![0x12, 0x34],
+        };
+        
+        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
+        
+        assert_eq!(tx.instruction_name(), "TransferWrapped");
+        assert_eq!(tx.amount(), Some(1_000_000));
+        assert_eq!(tx.target_chain(), Some(2));
+        assert_eq!(tx.target_chain_name(), Some("Ethereum"));
+        assert!(tx.is_outbound());
+        assert!(!tx.is_inbound());
+    }
+
+    #[test]
+    fn test_complete_transfer() {
+        let instruction = BridgeInstruction::CompleteTransfer {
+            vaa_hash: vec![],
+            is_native: false,
+        };
+        
+        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
+        
+        assert_eq!(tx.instruction_name(), "CompleteTransfer");
+        assert_eq!(tx.amount(), None);
+        assert!(!tx.is_outbound());
+        assert!(tx.is_inbound());
+    }
+
+    #[test]
+    fn test_attest_token() {
+        let instruction = BridgeInstruction::AttestToken;
+        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
+        
+        assert_eq!(tx.instruction_name(), "AttestToken");
+        assert!(tx.is_token_operation());
+        assert_eq!(tx.direction(), "Token Operation");
+    }
+
+    #[test]
+    fn test_display() {
+        let instruction = BridgeInstruction::TransferWrapped {
+            amount: 1_000_000,
+            target_chain: 30,
+            recipient: vec![],
+        };
+        
+        let tx = ParsedTransaction::new(BridgeType::Wormhole, instruction);
+        let display = format!("{}", tx);
+        
+        assert!(display.contains("Wormhole"));
+        assert!(display.contains("TransferWrapped"));
+        assert!(display.contains("1000000"));
+        assert!(display.contains("Base"));
+    }
+
+    #[test]
+    fn test_helpers_span_every_instruction() {
+        let at = |outer_index| InstructionLocation {
+            outer_index,
+            inner_index: None,
+        };
+        let transfer = |amount, target_chain| BridgeInstruction::TransferNative {
+            amount,
+            target_chain,
+            recipient: vec![0xcd],
+        };
+
+        let tx = ParsedTransaction::from_calls(
+            BridgeType::Wormhole,
+            vec![
+                BridgeCall::new(at(0), BridgeInstruction::AttestToken),
+                BridgeCall::new(at(1), transfer(400, 2)),
+                BridgeCall::new(at(2), transfer(600, 2)),
+            ],
+        );
+
+        assert_eq!(tx.instruction_name(), "AttestToken");
+        assert_eq!(tx.amount(), Some(1_000));
+        assert_eq!(tx.target_chain(), Some(2));
+        assert_eq!(tx.recipient(), Some(&[0xcd][..]));
+        assert!(tx.is_outbound() && tx.is_token_operation());
+        assert_eq!(tx.direction(), "Outbound");
+        assert!(format!("{}", tx).contains("AttestToken, TransferNative, TransferNative"));
+
+        let mixed = ParsedTransaction::from_calls(
+            BridgeType::Wormhole,
+            vec![BridgeCall::new(at(0), transfer(1, 2)), BridgeCall::new(at(1), transfer(1, 4))],
+        );
+        assert_eq!(mixed.target_chains(), [2, 4]);
+        assert_eq!(mixed.target_chain(), None);
+
+        let two_mints = ParsedTransaction::from_calls(
+            BridgeType::Wormhole,
+            vec![
+                BridgeCall::new(at(0), transfer(400, 2)).with_mint(Some("usdc".to_string())),
+                BridgeCall::new(at(1), transfer(600, 2)).with_mint(Some("wsol".to_string())),
+                BridgeCall::new(at(2), transfer(100, 2)).with_mint(Some("usdc".to_string())),
+            ],
+        );
+        assert_eq!(two_mints.amounts_by_mint(), [(Some("usdc"), 500), (Some("wsol"), 600)]);
+        assert_eq!(two_mints.amount(), None);
+    }
+}
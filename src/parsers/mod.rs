@@ -1,115 +1,646 @@
-pub mod bridge_types;
-pub mod wormhole;
-
-pub use bridge_types::{BridgeInstruction, BridgeType, ParsedTransaction};
-
-use crate::error::{Result, StauroXError};
-use solana_sdk::pubkey::Pubkey;
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
-use std::str::FromStr;
-use tracing::info;
-
-// Main parser that detects bridge type and extracts instruction data
-pub struct TransactionParser;
-
-impl TransactionParser {
-    pub fn new() -> Self {
-        Self
-    }
-
-    // Parse a transaction and extract bridge instruction details
-    pub fn parse_transaction(
-        &self,
-        tx: &EncodedConfirmedTransactionWithStatusMeta,
-    ) -> Result<Option<ParsedTransaction>> {
-        // Extract program IDs from transaction
-        let program_ids = self.extract_program_ids(tx)?;
-        
-        info!("Found {} program IDs in transaction", program_ids.len());
-        
-        // Detect bridge type
-        let bridge_type = self.detect_bridge_type(&program_ids)?;
-        
-        if bridge_type.is_none() {
-            info!("No bridge program detected");
-            return Ok(None);
-        }
-
-        info!("Detected bridge: {:?}", bridge_type);
-
-        // Parse specific bridge instruction
-        let instruction = self.parse_bridge_instruction(tx, bridge_type.unwrap())?;
-        
-        Ok(Some(ParsedTransaction {
-            bridge_type: bridge_type.unwrap(),
-            instruction,
-        }))
-    }
-
-    /// Extract program IDs from transaction
-    fn extract_program_ids(
-        &self,
-        tx: &EncodedConfirmedTransactionWithStatusMeta,
-    ) -> Result<Vec<Pubkey>> {
-        let account_keys = match &tx.transaction.transaction {
-            solana_transaction_status::EncodedTransaction::Json(ui_tx) => {
-                match &ui_tx.message {
-                    solana_transaction_status::UiMessage::Raw(msg) => {
-                        msg.account_keys.iter()
-                            .filter_map(|key| Pubkey::from_str(key).ok())
-                            .collect()
-                    }
-                    solana_transaction_status::UiMessage::Parsed(_) => {
-                        return Err(StauroXError::verification(
-                            "Parsed message format not supported"
-                        ));
-                    }
-                }
-            }
-            _ => {
-                return Err(StauroXError::verification(
-                    "Unsupported transaction encoding - expected JSON"
-                ));
-            }
-        };
-        
-        Ok(account_keys)
-    }
-
-    // Detect which bridge protocol was used
-    fn detect_bridge_type(&self, program_ids: &[Pubkey]) -> Result<Option<BridgeType>> {
-        const WORMHOLE_TOKEN_BRIDGE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
-        const WORMHOLE_CORE: &str = "worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth";
-        
-        for id in program_ids {
-            let id_str = id.to_string();
-            
-            if id_str == WORMHOLE_TOKEN_BRIDGE || id_str == WORMHOLE_CORE {
-                info!("✓ Detected Wormhole program: {}", id_str);
-                return Ok(Some(BridgeType::Wormhole));
-            }
-            // Add more bridges here
-        }
-        
-        Ok(None)
-    }
-
-    // Parse bridge-specific instruction data
-    fn parse_bridge_instruction(
-        &self,
-        tx: &EncodedConfirmedTransactionWithStatusMeta,
-        bridge_type: BridgeType,
-    ) -> Result<BridgeInstruction> {
-        match bridge_type {
-            BridgeType::Wormhole => wormhole::parse_wormhole_instruction(tx),
-            BridgeType::Across => Ok(BridgeInstruction::Unknown),
-            BridgeType::DeBridge => Ok(BridgeInstruction::Unknown),
-        }
-    }
-}
-
-impl Default for TransactionParser {
-    fn default() -> Self {
-        Self::new()
-    }
-}
\ No newline at end of file
+pub mod bridge_types;
+pub mod wormhole;
+
+pub use bridge_types::{
+    BridgeCall, BridgeInstruction, BridgeType, InstructionLocation, ParsedTransaction,
+};
+pub use wormhole::WormholeParser;
+
+use crate::config::Network;
+use crate::error::{Result, StauroXError};
+use solana_sdk::pubkey::Pubkey;
+use solana_sdk::transaction::VersionedTransaction;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiTransactionStatusMeta,
+    UiTransactionTokenBalance,
+};
+use std::str::FromStr;
+use tracing::{debug, info};
+
+/// An instruction as executed: top-level, or a CPI made by a top-level instruction
+pub struct InvokedInstruction {
+    pub location: InstructionLocation,
+    /// None when the program index is past every account key
+    pub program_id: Option<Pubkey>,
+    /// Accounts passed to the instruction, in order; None where the index is
+    /// past every key (lookup-table accounts of a transaction without meta)
+    pub accounts: Vec<Option<Pubkey>>,
+    pub data: Vec<u8>,
+}
+
+/// Every executed instruction of an RPC transaction, top-level ones followed by their CPIs
+///
+/// JSON transactions are read from their raw message; binary (base58/base64)
+/// ones are decoded into a `VersionedTransaction` first.
+pub(crate) fn invoked_instructions(
+    tx: &EncodedConfirmedTransactionWithStatusMeta,
+) -> Result<Vec<InvokedInstruction>> {
+    let meta = tx.transaction.meta.as_ref();
+
+    match &tx.transaction.transaction {
+        EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+            UiMessage::Raw(msg) => {
+                let account_keys = parse_account_keys(&msg.account_keys)?;
+                let top_level = msg
+                    .instructions
+                    .iter()
+                    .map(|ix| {
+                        let data = decode_instruction_data(&ix.data);
+                        (ix.program_id_index, ix.accounts.clone(), data)
+                    })
+                    .collect();
+
+                resolve_instructions(account_keys, top_level, meta)
+            }
+            UiMessage::Parsed(_) => Err(StauroXError::verification(
+                "Parsed message format not supported"
+            )),
+        },
+        encoded => {
+            let versioned = encoded.decode().ok_or_else(|| {
+                StauroXError::verification("Unsupported or undecodable transaction encoding")
+            })?;
+
+            versioned_instructions(&versioned, meta)
+        }
+    }
+}
+
+/// Every executed instruction of a decoded transaction
+///
+/// Without status meta only top-level instructions and static account keys are known.
+pub(crate) fn versioned_instructions(
+    tx: &VersionedTransaction,
+    meta: Option<&UiTransactionStatusMeta>,
+) -> Result<Vec<InvokedInstruction>> {
+    let top_level = tx
+        .message
+        .instructions()
+        .iter()
+        .map(|ix| (ix.program_id_index, ix.accounts.clone(), ix.data.clone()))
+        .collect();
+
+    resolve_instructions(tx.message.static_account_keys().to_vec(), top_level, meta)
+}
+
+/// Interleave CPIs from the meta and resolve program and account indices to keys
+///
+/// For v0 transactions the keys loaded from address lookup tables follow
+/// the static keys (writable, then readonly), matching how instructions
+/// index them, so a loaded key that fails to parse is an error rather than
+/// silently shifting the keys after it.
+fn resolve_instructions(
+    mut account_keys: Vec<Pubkey>,
+    top_level: Vec<(u8, Vec<u8>, Vec<u8>)>,
+    meta: Option<&UiTransactionStatusMeta>,
+) -> Result<Vec<InvokedInstruction>> {
+    let loaded_addresses =
+        meta.and_then(|meta| Option::<&UiLoadedAddresses>::from(meta.loaded_addresses.as_ref()));
+    if let Some(loaded) = loaded_addresses {
+        account_keys.extend(parse_account_keys(loaded.writable.iter().chain(&loaded.readonly))?);
+    }
+
+    let inner_instructions: &[UiInnerInstructions] = meta
+        .and_then(|meta| Option::<&Vec<_>>::from(meta.inner_instructions.as_ref()))
+        .map_or(&[], Vec::as_slice);
+
+    let key = |index: u8| account_keys.get(index as usize).copied();
+    let keys = |indices: &[u8]| indices.iter().map(|&index| key(index)).collect();
+
+    let mut invoked = Vec::new();
+    for (outer_index, (program_id_index, accounts, data)) in top_level.into_iter().enumerate() {
+        invoked.push(InvokedInstruction {
+            location: InstructionLocation { outer_index, inner_index: None },
+            program_id: key(program_id_index),
+            accounts: keys(&accounts),
+            data,
+        });
+
+        let cpis = inner_instructions
+            .iter()
+            .filter(|inner| inner.index as usize == outer_index)
+            .flat_map(|inner| &inner.instructions);
+
+        for (inner_index, cpi) in cpis.enumerate() {
+            // Inner instructions are only parsed for jsonParsed requests
+            if let UiInstruction::Compiled(cpi) = cpi {
+                invoked.push(InvokedInstruction {
+                    location: InstructionLocation {
+                        outer_index,
+                        inner_index: Some(inner_index),
+                    },
+                    program_id: key(cpi.program_id_index),
+                    accounts: keys(&cpi.accounts),
+                    data: decode_instruction_data(&cpi.data),
+                });
+            }
+        }
+    }
+
+    Ok(invoked)
+}
+
+/// Base58 account keys; any invalid key fails the whole list
+fn parse_account_keys<'a>(keys: impl IntoIterator<Item = &'a String>) -> Result<Vec<Pubkey>> {
+    keys.into_iter()
+        .map(|key| Pubkey::from_str(key))
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| StauroXError::verification(format!("Invalid account key: {}", e)))
+}
+
+/// Base58 instruction data as bytes; undecodable data reads as empty
+fn decode_instruction_data(data: &str) -> Vec<u8> {
+    bs58::decode(data).into_vec().unwrap_or_else(|_| {
+        debug!("Failed to decode instruction data");
+        Vec::new()
+    })
+}
+
+/// A bridge protocol: the programs that identify it and how its instructions decode
+///
+/// Implementations are registered on a `TransactionParser`, one per network.
+pub trait BridgeParser: Send + Sync {
+    fn bridge_type(&self) -> BridgeType;
+
+    /// Programs whose invocation marks a transaction as using this bridge
+    fn program_ids(&self) -> Vec<Pubkey>;
+
+    fn detect(&self, invoked: &[Pubkey]) -> bool {
+        let program_ids = self.program_ids();
+        invoked.iter().any(|id| program_ids.contains(id))
+    }
+
+    /// Decode this bridge's instructions, in execution order
+    fn parse_instructions(&self, instructions: &[InvokedInstruction]) -> Result<Vec<BridgeCall>>;
+}
+
+// Main parser that detects bridge type and extracts instruction data
+pub struct TransactionParser {
+    /// Checked in registration order; every parser that detects its bridge
+    /// contributes calls, and the first names the transaction's bridge
+    parsers: Vec<Box<dyn BridgeParser>>,
+}
+
+impl TransactionParser {
+    /// Parser for the built-in bridges on mainnet
+    pub fn new() -> Self {
+        Self::for_network(Network::Mainnet)
+    }
+
+    /// Parser for the built-in bridges, using the network's program IDs
+    pub fn for_network(network: Network) -> Self {
+        Self::empty().with_parser(Box::new(WormholeParser::for_network(network)))
+    }
+
+    /// Parser with no bridges registered
+    pub fn empty() -> Self {
+        Self {
+            parsers: Vec::new(),
+        }
+    }
+
+    pub fn with_parser(mut self, parser: Box<dyn BridgeParser>) -> Self {
+        self.parsers.push(parser);
+        self
+    }
+
+    // Parse a transaction and extract bridge instruction details
+    pub fn parse_transaction(
+        &self,
+        tx: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<Option<ParsedTransaction>> {
+        let meta = tx.transaction.meta.as_ref();
+        self.parse_instructions(&invoked_instructions(tx)?, meta)
+    }
+
+    /// Parse a decoded transaction, with its status meta when available
+    pub fn parse_versioned_transaction(
+        &self,
+        tx: &VersionedTransaction,
+        meta: Option<&UiTransactionStatusMeta>,
+    ) -> Result<Option<ParsedTransaction>> {
+        self.parse_instructions(&versioned_instructions(tx, meta)?, meta)
+    }
+
+    /// Parse a bincode-serialized transaction as sent over the wire
+    ///
+    /// Raw bytes carry no status meta, so CPIs and lookup-table accounts are not seen.
+    pub fn parse_transaction_bytes(&self, bytes: &[u8]) -> Result<Option<ParsedTransaction>> {
+        let tx: VersionedTransaction = bincode::deserialize(bytes).map_err(|e| {
+            StauroXError::verification(format!("Invalid transaction bytes: {}", e))
+        })?;
+
+        self.parse_versioned_transaction(&tx, None)
+    }
+
+    fn parse_instructions(
+        &self,
+        instructions: &[InvokedInstruction],
+        meta: Option<&UiTransactionStatusMeta>,
+    ) -> Result<Option<ParsedTransaction>> {
+        // Extract the programs invoked, directly or through CPI
+        let program_ids = self.extract_program_ids(instructions);
+        
+        info!("Found {} invoked program IDs in transaction", program_ids.len());
+        
+        // Detect bridge types
+        let bridges = self.detect_bridges(&program_ids);
+        let Some(first) = bridges.first() else {
+            info!("No bridge program detected");
+            return Ok(None);
+        };
+
+        // Parse every bridge instruction, in execution order
+        let mut calls = Vec::new();
+        for bridge in &bridges {
+            info!("Detected bridge: {:?}", bridge.bridge_type());
+            calls.extend(bridge.parse_instructions(instructions)?);
+        }
+        calls.sort_by_key(|call| call.location);
+
+        // Prefer the mint the bridge calls name over guessing from token balances
+        let parsed = ParsedTransaction::from_calls(first.bridge_type(), calls);
+        let mint = parsed
+            .call_mint()
+            .map(str::to_string)
+            .or_else(|| meta.and_then(|meta| self.extract_transferred_mint(meta)));
+
+        Ok(Some(parsed.with_mint(mint)))
+    }
+
+    /// Mint of the token balances the transaction touched, if there is exactly one
+    ///
+    /// Fallback for bridges whose calls name no mint; swaps and fee tokens
+    /// usually touch several mints, leaving this None.
+    fn extract_transferred_mint(&self, meta: &UiTransactionStatusMeta) -> Option<String> {
+        let balances = [&meta.pre_token_balances, &meta.post_token_balances];
+
+        let mut mints = balances
+            .into_iter()
+            .filter_map(|balances| Option::from(balances.as_ref()))
+            .flat_map(|balances: &Vec<UiTransactionTokenBalance>| balances.iter())
+            .map(|balance| balance.mint.as_str());
+
+        let mint = mints.next()?;
+        if mints.all(|other| other == mint) {
+            Some(mint.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Program IDs of top-level and inner (CPI) instructions, deduplicated
+    fn extract_program_ids(&self, instructions: &[InvokedInstruction]) -> Vec<Pubkey> {
+        let mut program_ids: Vec<Pubkey> = Vec::new();
+        for program_id in instructions.iter().filter_map(|ix| ix.program_id) {
+            if !program_ids.contains(&program_id) {
+                program_ids.push(program_id);
+            }
+        }
+
+        program_ids
+    }
+
+    // Detect which bridge protocol was used
+    fn detect_bridges(&self, program_ids: &[Pubkey]) -> Vec<&dyn BridgeParser> {
+        self.parsers
+            .iter()
+            .map(|parser| parser.as_ref())
+            .filter(|parser| parser.detect(program_ids))
+            .collect()
+    }
+}
+
+impl Default for TransactionParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::CompiledInstruction;
+    use solana_sdk::message::{Message, MessageHeader, VersionedMessage};
+    use solana_sdk::signature::Signature;
+    use solana_transaction_status::option_serializer::OptionSerializer;
+    use solana_transaction_status::{
+        EncodableWithMeta, EncodedTransaction, EncodedTransactionWithStatusMeta,
+        TransactionStatusMeta, UiCompiledInstruction, UiMessage, UiRawMessage, UiTransaction,
+        UiTransactionEncoding, UiTransactionStatusMeta,
+    };
+
+    const AGGREGATOR: &str = "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4";
+    const TOKEN_BRIDGE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
+
+    fn compiled(program_id_index: u8, data: &[u8]) -> UiCompiledInstruction {
+        UiCompiledInstruction {
+            program_id_index,
+            accounts: vec![0],
+            data: bs58::encode(data).into_string(),
+            stack_height: None,
+        }
+    }
+
+    fn transfer_native_data(amount: u64, target_chain: u16) -> Vec<u8> {
+        let mut data = vec![0x01];
+        data.extend_from_slice(&7u32.to_le_bytes());
+        data.extend_from_slice(&amount.to_le_bytes());
+        data.extend_from_slice(&0u64.to_le_bytes());
+        data.extend_from_slice(&[0xab; 32]);
+        data.extend_from_slice(&target_chain.to_le_bytes());
+        data
+    }
+
+    /// Transaction whose only top-level instruction is an aggregator that CPIs into the token bridge
+    fn aggregator_transfer() -> EncodedConfirmedTransactionWithStatusMeta {
+        let mut meta = UiTransactionStatusMeta::from(TransactionStatusMeta::default());
+        meta.inner_instructions = OptionSerializer::Some(vec![UiInnerInstructions {
+            index: 0,
+            instructions: vec![
+                UiInstruction::Compiled(compiled(1, &[0x09])),
+                UiInstruction::Compiled(compiled(2, &transfer_native_data(5_000, 2))),
+            ],
+        }]);
+
+        EncodedConfirmedTransactionWithStatusMeta {
+            slot: 100,
+            transaction: EncodedTransactionWithStatusMeta {
+                transaction: EncodedTransaction::Json(UiTransaction {
+                    signatures: vec![],
+                    message: UiMessage::Raw(UiRawMessage {
+                        header: MessageHeader::default(),
+                        account_keys: vec![
+                            Pubkey::new_unique().to_string(),
+                            AGGREGATOR.to_string(),
+                            TOKEN_BRIDGE.to_string(),
+                        ],
+                        recent_blockhash: String::new(),
+                        instructions: vec![compiled(1, &[0x01])],
+                        address_table_lookups: None,
+                    }),
+                }),
+                meta: Some(meta),
+                version: None,
+            },
+            block_time: None,
+        }
+    }
+
+    #[test]
+    fn test_detects_bridge_call_inside_cpi() {
+        let parsed = TransactionParser::new()
+            .parse_transaction(&aggregator_transfer())
+            .unwrap()
+            .expect("Wormhole transfer made through CPI");
+
+        assert_eq!(parsed.bridge_type, BridgeType::Wormhole);
+        assert_eq!(parsed.amount(), Some(5_000));
+        assert_eq!(parsed.target_chain(), Some(2));
+        assert_eq!(parsed.instructions.len(), 1);
+        assert_eq!(
+            parsed.instructions[0].location,
+            InstructionLocation { outer_index: 0, inner_index: Some(1) }
+        );
+    }
+
+    #[test]
+    fn test_resolves_programs_from_lookup_tables() {
+        let mut tx = aggregator_transfer();
+        let EncodedTransaction::Json(ui_tx) = &mut tx.transaction.transaction else {
+            unreachable!()
+        };
+        let UiMessage::Raw(msg) = &mut ui_tx.message else {
+            unreachable!()
+        };
+
+        // Token bridge loaded from a lookup table, after one loaded writable key
+        msg.account_keys.pop();
+        let payer = Pubkey::from_str(&msg.account_keys[0]).unwrap();
+        let meta = tx.transaction.meta.as_mut().unwrap();
+        meta.loaded_addresses = OptionSerializer::Some(UiLoadedAddresses {
+            writable: vec![Pubkey::new_unique().to_string()],
+            readonly: vec![TOKEN_BRIDGE.to_string()],
+        });
+        let mut bridge_call = compiled(3, &transfer_native_data(9_000, 4));
+        bridge_call.accounts = vec![0, 3, 42];
+        if let OptionSerializer::Some(inner) = &mut meta.inner_instructions {
+            inner[0].instructions[1] = UiInstruction::Compiled(bridge_call);
+        }
+
+        let parsed = TransactionParser::new().parse_transaction(&tx).unwrap().unwrap();
+        assert_eq!(parsed.amount(), Some(9_000));
+        assert_eq!(parsed.target_chain(), Some(4));
+
+        // Accounts resolve through the lookup table too
+        let invoked = invoked_instructions(&tx).unwrap();
+        assert_eq!(
+            invoked[2].accounts,
+            [Some(payer), Some(Pubkey::from_str(TOKEN_BRIDGE).unwrap()), None]
+        );
+
+        // An index past every key is skipped rather than panicking
+        let meta = tx.transaction.meta.as_mut().unwrap();
+        if let OptionSerializer::Some(inner) = &mut meta.inner_instructions {
+            inner[0].instructions[1] = UiInstruction::Compiled(compiled(42, &[0x01]));
+        }
+        assert!(TransactionParser::new().parse_transaction(&tx).unwrap().is_none());
+
+        // A malformed loaded key fails instead of shifting the keys after it
+        let meta = tx.transaction.meta.as_mut().unwrap();
+        meta.loaded_addresses = OptionSerializer::Some(UiLoadedAddresses {
+            writable: vec!["not-a-pubkey".to_string()],
+            readonly: vec![TOKEN_BRIDGE.to_string()],
+        });
+        assert!(TransactionParser::new().parse_transaction(&tx).is_err());
+    }
+
+    /// Legacy transaction calling the token bridge directly
+    fn direct_transfer() -> VersionedTransaction {
+        legacy_bridge_transaction(vec![transfer_native_data(7_500, 5)])
+    }
+
+    /// Legacy transaction with one top-level token bridge call per data entry
+    fn legacy_bridge_transaction(calls: Vec<Vec<u8>>) -> VersionedTransaction {
+        let keys = vec![Pubkey::new_unique(), Pubkey::from_str(TOKEN_BRIDGE).unwrap()];
+        let instructions = calls
+            .into_iter()
+            .map(|data| CompiledInstruction::new_from_raw_parts(1, data, vec![0]))
+            .collect();
+
+        VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new_with_compiled_instructions(
+                1,
+                0,
+                1,
+                keys,
+                Hash::default(),
+                instructions,
+            )),
+        }
+    }
+
+    #[test]
+    fn test_parses_binary_encodings() {
+        let tx = direct_transfer();
+        let parser = TransactionParser::new();
+
+        let bytes = bincode::serialize(&tx).unwrap();
+        let from_bytes = parser.parse_transaction_bytes(&bytes).unwrap().unwrap();
+        assert_eq!(from_bytes.amount(), Some(7_500));
+        assert_eq!(from_bytes.target_chain(), Some(5));
+
+        let meta = TransactionStatusMeta::default();
+        for encoding in [UiTransactionEncoding::Base64, UiTransactionEncoding::Base58] {
+            let encoded = EncodedConfirmedTransactionWithStatusMeta {
+                slot: 100,
+                transaction: EncodedTransactionWithStatusMeta {
+                    transaction: tx.encode_with_meta(encoding, &meta),
+                    meta: Some(UiTransactionStatusMeta::from(meta.clone())),
+                    version: None,
+                },
+                block_time: None,
+            };
+
+            let parsed = parser.parse_transaction(&encoded).unwrap().unwrap();
+            assert_eq!(parsed.amount(), from_bytes.amount());
+            assert_eq!(parsed.instructions[0].location, from_bytes.instructions[0].location);
+        }
+
+        assert!(parser.parse_transaction_bytes(&bytes[..8]).is_err());
+    }
+
+    #[test]
+    fn test_keeps_every_bridge_instruction() {
+        let tx = legacy_bridge_transaction(vec![
+            vec![0x02],
+            transfer_native_data(1_000, 2),
+            transfer_native_data(2_500, 2),
+        ]);
+
+        let parsed = TransactionParser::new()
+            .parse_versioned_transaction(&tx, None)
+            .unwrap()
+            .unwrap();
+
+        let names: Vec<&str> = parsed.instructions.iter().map(BridgeCall::name).collect();
+        assert_eq!(names, ["AttestToken", "TransferNative", "TransferNative"]);
+        let outer: Vec<usize> = parsed
+            .instructions
+            .iter()
+            .map(|call| call.location.outer_index)
+            .collect();
+        assert_eq!(outer, [0, 1, 2]);
+        assert_eq!(parsed.amount(), Some(3_500));
+        assert_eq!(parsed.direction(), "Outbound");
+    }
+
+    #[test]
+    fn test_mint_read_from_transfer_accounts() {
+        let mint = Pubkey::new_unique();
+        let keys = vec![
+            Pubkey::new_unique(),
+            Pubkey::from_str(TOKEN_BRIDGE).unwrap(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            mint,
+        ];
+        // payer, config, from, mint
+        let transfer = |amount| {
+            CompiledInstruction::new_from_raw_parts(
+                1,
+                transfer_native_data(amount, 2),
+                vec![0, 2, 3, 4],
+            )
+        };
+        let message = |instructions| {
+            VersionedMessage::Legacy(Message::new_with_compiled_instructions(
+                1,
+                0,
+                1,
+                keys.clone(),
+                Hash::default(),
+                instructions,
+            ))
+        };
+        let parse = |message| {
+            let tx = VersionedTransaction { signatures: vec![Signature::default()], message };
+            TransactionParser::new().parse_versioned_transaction(&tx, None).unwrap().unwrap()
+        };
+
+        let parsed = parse(message(vec![transfer(1_000), transfer(2_000)]));
+        assert_eq!(parsed.mint, Some(mint.to_string()));
+        assert_eq!(parsed.instructions[1].mint, Some(mint.to_string()));
+
+        // A transfer whose mint account is missing leaves the mint unknown
+        let short = CompiledInstruction::new_from_raw_parts(1, transfer_native_data(5, 2), vec![0]);
+        assert_eq!(parse(message(vec![transfer(1_000), short])).mint, None);
+    }
+
+    /// Stand-in for a bridge registered by a downstream crate
+    struct AggregatorBridge;
+
+    impl BridgeParser for AggregatorBridge {
+        fn bridge_type(&self) -> BridgeType {
+            BridgeType::Other("Jupiter".to_string())
+        }
+
+        fn program_ids(&self) -> Vec<Pubkey> {
+            vec![Pubkey::from_str(AGGREGATOR).unwrap()]
+        }
+
+        fn parse_instructions(&self, instructions: &[InvokedInstruction]) -> Result<Vec<BridgeCall>> {
+            let program_ids = self.program_ids();
+            Ok(instructions
+                .iter()
+                .filter(|ix| ix.program_id.is_some_and(|id| program_ids.contains(&id)))
+                .map(|ix| {
+                    let route = BridgeInstruction::Custom {
+                        name: "Route".to_string(),
+                        amount: None,
+                        target_chain: None,
+                    };
+                    BridgeCall::new(ix.location, route)
+                })
+                .collect())
+        }
+    }
+
+    #[test]
+    fn test_registry_and_network_program_ids() {
+        let tx = aggregator_transfer();
+
+        // Mainnet token bridge IDs mean nothing on devnet
+        assert!(TransactionParser::for_network(Network::Devnet)
+            .parse_transaction(&tx)
+            .unwrap()
+            .is_none());
+
+        let parsed = TransactionParser::empty()
+            .with_parser(Box::new(AggregatorBridge))
+            .with_parser(Box::new(WormholeParser::for_network(Network::Mainnet)))
+            .parse_transaction(&tx)
+            .unwrap()
+            .unwrap();
+        assert_eq!(parsed.bridge_type, BridgeType::Other("Jupiter".to_string()));
+        assert_eq!(parsed.bridge_name(), "Jupiter");
+        assert_eq!(parsed.instruction_name(), "Route");
+
+        // The Wormhole transfer the route makes is kept alongside its calls
+        let locations: Vec<_> = parsed.instructions.iter().map(|call| call.location).collect();
+        assert_eq!(
+            locations,
+            [
+                InstructionLocation { outer_index: 0, inner_index: None },
+                InstructionLocation { outer_index: 0, inner_index: Some(0) },
+                InstructionLocation { outer_index: 0, inner_index: Some(1) },
+            ]
+        );
+        assert_eq!(parsed.instructions[2].name(), "TransferNative");
+        assert_eq!(parsed.amount(), Some(5_000));
+
+        assert!(TransactionParser::empty().parse_transaction(&tx).unwrap().is_none());
+    }
+}
@@ -1,190 +1,234 @@
-use crate::error::{Result, StauroXError};
-use super::bridge_types::BridgeInstruction;
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
-use tracing::debug;
-
-const WORMHOLE_TOKEN_BRIDGE: &str = "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb";
-
-// Instruction discriminators
-const TRANSFER_NATIVE: u8 = 0x01;
-const ATTEST_TOKEN: u8 = 0x02;
-const COMPLETE_TRANSFER: u8 = 0x03;
-const TRANSFER_WRAPPED: u8 = 0x04;
-const TRANSFER_TOKENS_WITH_PAYLOAD: u8 = 0x05;
-const COMPLETE_TRANSFER_NATIVE: u8 = 0x07;
-const CREATE_WRAPPED: u8 = 0x09;
-const COMPLETE_WRAPPED: u8 = 0x0a;
-const COMPLETE_TRANSFER_WITH_PAYLOAD: u8 = 0x0d;
-
-pub fn parse_wormhole_instruction(
-    tx: &EncodedConfirmedTransactionWithStatusMeta,
-) -> Result<BridgeInstruction> {
-    let (account_keys, instructions) = match &tx.transaction.transaction {
-        solana_transaction_status::EncodedTransaction::Json(ui_tx) => {
-            match &ui_tx.message {
-                solana_transaction_status::UiMessage::Raw(msg) => {
-                    (&msg.account_keys, &msg.instructions)
-                }
-                solana_transaction_status::UiMessage::Parsed(_) => {
-                    return Err(StauroXError::verification("Parsed message not supported"));
-                }
-            }
-        }
-        _ => {
-            return Err(StauroXError::verification("Unsupported transaction encoding"));
-        }
-    };
-
-    for ix in instructions {
-        let program_id = &account_keys[ix.program_id_index as usize];
-        
-        if program_id != WORMHOLE_TOKEN_BRIDGE {
-            continue;
-        }
-
-        let data = match bs58::decode(&ix.data).into_vec() {
-            Ok(d) => d,
-            Err(_) => {
-                debug!("Failed to decode instruction data");
-                continue;
-            }
-        };
-
-        if data.is_empty() {
-            continue;
-        }
-
-        let discriminator = data[0];
-        
-        return match discriminator {
-            TRANSFER_NATIVE | TRANSFER_WRAPPED | TRANSFER_TOKENS_WITH_PAYLOAD => {
-                parse_transfer_instruction(discriminator, &data)
-            }
-            ATTEST_TOKEN => {
-                parse_attest_token()
-            }
-            COMPLETE_TRANSFER | COMPLETE_TRANSFER_NATIVE => {
-                parse_complete_transfer(discriminator)
-            }
-            CREATE_WRAPPED | COMPLETE_WRAPPED => {
-                parse_wrapped_token_instruction(discriminator)
-            }
-            COMPLETE_TRANSFER_WITH_PAYLOAD => {
-                parse_complete_transfer_with_payload(&data)
-            }
-            _ => {
-                debug!("Unknown Wormhole instruction: 0x{:02x}", discriminator);
-                Ok(BridgeInstruction::Unknown)
-            }
-        };
-    }
-
-    Ok(BridgeInstruction::Unknown)
-}
-
-fn parse_transfer_instruction(discriminator: u8, data: &[u8]) -> Result<BridgeInstruction> {
-    // TransferNative (0x01), TransferWrapped (0x04), TransferTokensWithPayload (0x05)
-    // All share the same layout: disc(1) + nonce(4) + amount(8) + fee(8) + recipient(32) + chain(2) = 55 bytes
-    
-    if data.len() < 55 {
-        debug!("Transfer instruction too short: {} bytes", data.len());
-        return Ok(BridgeInstruction::Unknown);
-    }
-
-    let _nonce = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
-    
-    let amount = u64::from_le_bytes([
-        data[5], data[6], data[7], data[8],
-        data[9], data[10], data[11], data[12],
-    ]);
-    
-    let _fee = u64::from_le_bytes([
-        data[13], data[14], data[15], data[16],
-        data[17], data[18], data[19], data[20],
-    ]);
-    
-    let recipient = data[21..53].to_vec();
-    let target_chain = u16::from_le_bytes([data[53], data[54]]);
-
-    debug!(
-        "Parsed Wormhole transfer (0x{:02x}): amount={}, chain={}, recipient={}",
-        discriminator, amount, target_chain, hex::encode(&recipient)
-    );
-
-    Ok(match discriminator {
-        TRANSFER_WRAPPED => BridgeInstruction::TransferWrapped {
-            amount,
-            target_chain,
-            recipient,
-        },
-        TRANSFER_NATIVE => BridgeInstruction::TransferNative {
-            amount,
-            target_chain,
-            recipient,
-        },
-        TRANSFER_TOKENS_WITH_PAYLOAD => BridgeInstruction::TransferWithPayload {
-            amount,
-            target_chain,
-        },
-        _ => BridgeInstruction::Unknown,
-    })
-}
-
-fn parse_attest_token() -> Result<BridgeInstruction> {
-    // AttestToken (0x02): Register a new token for bridging
-    // Instruction data is ONLY the discriminator (1 byte)
-    // Token attestation data is stored in accounts
-    
-    debug!("Parsed AttestToken instruction");
-    
-    Ok(BridgeInstruction::AttestToken)
-}
-
-fn parse_complete_transfer(discriminator: u8) -> Result<BridgeInstruction> {
-    // CompleteTransfer (0x03), CompleteTransferNative (0x07)
-    // Receive tokens on Solana from another chain
-    // Instruction data is ONLY the discriminator (1 byte)
-    // VAA (Verified Action Approval) data is stored in accounts
-    
-    debug!(
-        "Parsed CompleteTransfer: discriminator=0x{:02x}, is_native={}",
-        discriminator, 
-        discriminator == COMPLETE_TRANSFER_NATIVE
-    );
-    
-    Ok(BridgeInstruction::CompleteTransfer {
-        vaa_hash: vec![], // Empty - VAA is in accounts
-        is_native: discriminator == COMPLETE_TRANSFER_NATIVE,
-    })
-}
-
-fn parse_wrapped_token_instruction(discriminator: u8) -> Result<BridgeInstruction> {
-    // CreateWrapped (0x09), CompleteWrapped (0x0a)
-    // Token creation/completion operations
-    // Instruction data is minimal (just discriminator)
-    
-    debug!(
-        "Wrapped token instruction: discriminator=0x{:02x}",
-        discriminator
-    );
-    
-    Ok(BridgeInstruction::WrappedTokenOperation {
-        operation_type: if discriminator == CREATE_WRAPPED {
-            "CreateWrapped".to_string()
-        } else {
-            "CompleteWrapped".to_string()
-        },
-    })
-}
-
-fn parse_complete_transfer_with_payload(data: &[u8]) -> Result<BridgeInstruction> {
-    // CompleteTransferWithPayload (0x0d): Receive transfer with metadata
-    // Similar to CompleteTransfer but includes payload
-    
-    debug!(
-        "Parsed CompleteTransferWithPayload: length={}",
-        data.len()
-    );
-    
-    Ok(BridgeInstruction::CompleteTransferWithPayload)
+use crate::config::Network;
+use crate::error::Result;
+use super::bridge_types::{BridgeCall, BridgeInstruction, BridgeType};
+use super::{BridgeParser, InvokedInstruction};
+use solana_sdk::pubkey::Pubkey;
+use tracing::debug;
+
+// Program IDs per network
+const MAINNET_CORE: Pubkey = solana_sdk::pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+const MAINNET_TOKEN_BRIDGE: Pubkey =
+    solana_sdk::pubkey!("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+const DEVNET_CORE: Pubkey = solana_sdk::pubkey!("3u8hJUVTA4jH1wYAyUur7FFZVQ8H635K3tSHHF4ssjQ5");
+const DEVNET_TOKEN_BRIDGE: Pubkey =
+    solana_sdk::pubkey!("DZnkkTmCiFWfYTfT41X3Rd1kDgozqzxWaHqsw6W4x2oe");
+
+// Instruction discriminators
+const TRANSFER_NATIVE: u8 = 0x01;
+const ATTEST_TOKEN: u8 = 0x02;
+const COMPLETE_TRANSFER: u8 = 0x03;
+const TRANSFER_WRAPPED: u8 = 0x04;
+const TRANSFER_TOKENS_WITH_PAYLOAD: u8 = 0x05;
+const COMPLETE_TRANSFER_NATIVE: u8 = 0x07;
+const CREATE_WRAPPED: u8 = 0x09;
+const COMPLETE_WRAPPED: u8 = 0x0a;
+const COMPLETE_TRANSFER_WITH_PAYLOAD: u8 = 0x0d;
+
+// Position of the mint among a transfer's accounts
+// (payer, config, from, mint, ... and payer, config, from, from_owner, wrapped_mint, ...)
+const TRANSFER_NATIVE_MINT_ACCOUNT: usize = 3;
+const TRANSFER_WRAPPED_MINT_ACCOUNT: usize = 4;
+
+/// Wormhole core and token bridge programs
+///
+/// Either program marks a transaction as Wormhole; only token bridge
+/// instructions are decoded.
+pub struct WormholeParser {
+    core: Pubkey,
+    token_bridge: Pubkey,
+}
+
+impl WormholeParser {
+    pub fn for_network(network: Network) -> Self {
+        match network {
+            Network::Mainnet => Self {
+                core: MAINNET_CORE,
+                token_bridge: MAINNET_TOKEN_BRIDGE,
+            },
+            Network::Devnet => Self {
+                core: DEVNET_CORE,
+                token_bridge: DEVNET_TOKEN_BRIDGE,
+            },
+        }
+    }
+}
+
+impl BridgeParser for WormholeParser {
+    fn bridge_type(&self) -> BridgeType {
+        BridgeType::Wormhole
+    }
+
+    fn program_ids(&self) -> Vec<Pubkey> {
+        vec![self.token_bridge, self.core]
+    }
+
+    fn parse_instructions(&self, instructions: &[InvokedInstruction]) -> Result<Vec<BridgeCall>> {
+        parse_token_bridge_instructions(&self.token_bridge, instructions)
+    }
+}
+
+/// Every token bridge instruction in execution order, including ones made through CPI
+fn parse_token_bridge_instructions(
+    token_bridge: &Pubkey,
+    instructions: &[InvokedInstruction],
+) -> Result<Vec<BridgeCall>> {
+    let mut calls = Vec::new();
+
+    for ix in instructions {
+        if ix.program_id.as_ref() != Some(token_bridge) {
+            continue;
+        }
+
+        let data = &ix.data;
+        if data.is_empty() {
+            continue;
+        }
+
+        let discriminator = data[0];
+        
+        let instruction = match discriminator {
+            TRANSFER_NATIVE | TRANSFER_WRAPPED | TRANSFER_TOKENS_WITH_PAYLOAD => {
+                parse_transfer_instruction(discriminator, data)
+            }
+            ATTEST_TOKEN => {
+                parse_attest_token()
+            }
+            COMPLETE_TRANSFER | COMPLETE_TRANSFER_NATIVE => {
+                parse_complete_transfer(discriminator)
+            }
+            CREATE_WRAPPED | COMPLETE_WRAPPED => {
+                parse_wrapped_token_instruction(discriminator)
+            }
+            COMPLETE_TRANSFER_WITH_PAYLOAD => {
+                parse_complete_transfer_with_payload(data)
+            }
+            _ => {
+                debug!("Unknown Wormhole instruction: 0x{:02x}", discriminator);
+                Ok(BridgeInstruction::Unknown)
+            }
+        }?;
+
+        debug!("Wormhole call at {:?}", ix.location);
+        let mint = transfer_mint(discriminator, ix).map(|mint| mint.to_string());
+        calls.push(BridgeCall::new(ix.location, instruction).with_mint(mint));
+    }
+
+    Ok(calls)
+}
+
+/// Mint account of a native or wrapped transfer
+fn transfer_mint(discriminator: u8, ix: &InvokedInstruction) -> Option<Pubkey> {
+    let index = match discriminator {
+        TRANSFER_NATIVE => TRANSFER_NATIVE_MINT_ACCOUNT,
+        TRANSFER_WRAPPED => TRANSFER_WRAPPED_MINT_ACCOUNT,
+        _ => return None,
+    };
+    ix.accounts.get(index).copied().flatten()
+}
+
+fn parse_transfer_instruction(discriminator: u8, data: &[u8]) -> Result<BridgeInstruction> {
+    // TransferNative (0x01), TransferWrapped (0x04), TransferTokensWithPayload (0x05)
+    // All share the same layout: disc(1) + nonce(4) + amount(8) + fee(8) + recipient(32) + chain(2) = 55 bytes
+    
+    if data.len() < 55 {
+        debug!("Transfer instruction too short: {} bytes", data.len());
+        return Ok(BridgeInstruction::Unknown);
+    }
+
+    let _nonce = u32::from_le_bytes([data[1], data[2], data[3], data[4]]);
+    
+    let amount = u64::from_le_bytes([
+        data[5], data[6], data[7], data[8],
+        data[9], data[10], data[11], data[12],
+    ]);
+    
+    let _fee = u64::from_le_bytes([
+        data[13], data[14], data[15], data[16],
+        data[17], data[18], data[19], data[20],
+    ]);
+    
+    let recipient = data[21..53].to_vec();
+    let target_chain = u16::from_le_bytes([data[53], data[54]]);
+
+    debug!(
+        "Parsed Wormhole transfer (0x{:02x}): amount={}, chain={}, recipient={}",
+        discriminator, amount, target_chain, hex::encode(&recipient)
+    );
+
+    Ok(match discriminator {
+        TRANSFER_WRAPPED => BridgeInstruction::TransferWrapped {
+            amount,
+            target_chain,
+            recipient,
+        },
+        TRANSFER_NATIVE => BridgeInstruction::TransferNative {
+            amount,
+            target_chain,
+            recipient,
+        },
+        TRANSFER_TOKENS_WITH_PAYLOAD => BridgeInstruction::TransferWithPayload {
+            amount,
+            target_chain,
+        },
+        _ => BridgeInstruction::Unknown,
+    })
+}
+
+fn parse_attest_token() -> Result<BridgeInstruction> {
+    // AttestToken (0x02): Register a new token for bridging
+    // Instruction data is ONLY the discriminator (1 byte)
+    // Token attestation data is stored in accounts
+    
+    debug!("Parsed AttestToken instruction");
+    
+    Ok(BridgeInstruction::AttestToken)
+}
+
+fn parse_complete_transfer(discriminator: u8) -> Result<BridgeInstruction> {
+    // CompleteTransfer (0x03), CompleteTransferNative (0x07)
+    // Receive tokens on Solana from another chain
+    // Instruction data is ONLY the discriminator (1 byte)
+    // VAA (Verified Action Approval) data is stored in accounts
+    
+    debug!(
+        "Parsed CompleteTransfer: discriminator=0x{:02x}, is_native={}",
+        discriminator, 
+        discriminator == COMPLETE_TRANSFER_NATIVE
+    );
+    
+    Ok(BridgeInstruction::CompleteTransfer {
+        vaa_hash: vec![], // Empty - VAA is in accounts
+        is_native: discriminator == COMPLETE_TRANSFER_NATIVE,
+    })
+}
+
+fn parse_wrapped_token_instruction(discriminator: u8) -> Result<BridgeInstruction> {
+    // CreateWrapped (0x09), CompleteWrapped (0x0a)
+    // Token creation/completion operations
+    // Instruction data is minimal (just discriminator)
+    
+    debug!(
+        "Wrapped token instruction: discriminator=0x{:02x}",
+        discriminator
+    );
+    
+    Ok(BridgeInstruction::WrappedTokenOperation {
+        operation_type: if discriminator == CREATE_WRAPPED {
+            "CreateWrapped".to_string()
+        } else {
+            "CompleteWrapped".to_string()
+        },
+    })
+}
+
+fn parse_complete_transfer_with_payload(data: &[u8]) -> Result<BridgeInstruction> {
+    // CompleteTransferWithPayload (0x0d): Receive transfer with metadata
+    // Similar to CompleteTransfer but includes payload
+    
+    debug!(
+        "Parsed CompleteTransferWithPayload: length={}",
+        data.len()
+    );
+    
+    Ok(BridgeInstruction::CompleteTransferWithPayload)
 }
\ No newline at end of file
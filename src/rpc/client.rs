@@ -1,144 +1,830 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::signature::Signature;
-use solana_transaction_status::{EncodedConfirmedTransactionWithStatusMeta, UiTransactionEncoding};
-use std::sync::Arc;
-use tracing::{debug, warn};
-
-use crate::error::Result;
-use super::consensus::ConsensusEngine;
-
-/// Multi-RPC client with consensus verification
-pub struct MultiRpcClient {
-    clients: Vec<Arc<RpcClient>>,
-    consensus: ConsensusEngine,
-}
-
-impl MultiRpcClient {
-    pub fn new(rpc_urls: Vec<String>, consensus_threshold: usize) -> Self {
-        let clients: Vec<_> = rpc_urls
-            .into_iter()
-            .map(|url| Arc::new(RpcClient::new(url)))
-            .collect();
-
-        let consensus = ConsensusEngine::new(consensus_threshold, clients.len());
-
-        Self { clients, consensus }
-    }
-
-    /// Fetch transaction from multiple RPCs with consensus
-    pub async fn fetch_transaction_with_consensus(
-        &self,
-        signature: &Signature,
-    ) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
-        let signature = *signature;
-    
-        let results = self.fetch_from_all_rpcs(move |client| {
-            // Enable versioned transactions support
-            let config = solana_client::rpc_config::RpcTransactionConfig {
-                encoding: Some(UiTransactionEncoding::Json),
-                commitment: Some(solana_sdk::commitment_config::CommitmentConfig::confirmed()),
-                max_supported_transaction_version: Some(0),  // CRITICAL: Support v0 transactions
-            };
-            client.get_transaction_with_config(&signature, config)
-        }).await;
-
-        self.consensus.has_minimum_responses(&results)?;
-    
-        debug!(
-            "Transaction consensus: {}/{} RPCs responded",
-            results.len(),
-            self.clients.len()
-        );
-
-        // Return first result
-        results.into_iter().next().ok_or_else(|| {
-            crate::error::StauroXError::consensus_failure(0, self.consensus.threshold())
-        })
-    }
-
-    /// Get current slot from multiple RPCs with consensus
-    pub async fn get_slot_with_consensus(&self) -> Result<u64> {
-        let slots = self.fetch_from_all_rpcs(|client| client.get_slot()).await;
-
-        let consensus_slot = self.consensus.find_consensus(slots)?;
-
-        debug!("Slot consensus achieved: {}", consensus_slot);
-        Ok(consensus_slot)
-    }
-
-    
-    /// Generic method to fetch from all RPCs in parallel
-    async fn fetch_from_all_rpcs<T, F>(&self, fetch_fn: F) -> Vec<T>
-    where
-        T: Send + 'static,
-        F: Fn(&RpcClient) -> solana_client::client_error::Result<T> + Send + 'static + Clone,
-    {
-        let mut handles = vec![];
-
-        for (idx, client) in self.clients.iter().enumerate() {
-            let client = Arc::clone(client);
-            let fetch_fn = fetch_fn.clone();
-
-            let handle = tokio::spawn(async move {
-                match fetch_fn(&client) {
-                    Ok(result) => Some((idx, result)),
-                    Err(e) => {
-                        warn!("RPC {} failed: {}", idx, e);
-                        None
-                    }
-                }
-            });
-
-            handles.push(handle);
-        }
-
-        // Collect results as they come in, stop when we have enough
-        let mut results = vec![];
-        let threshold = self.consensus.threshold();
-        
-        while !handles.is_empty() && results.len() < threshold.max(self.clients.len()) {
-            // Race all remaining handles
-            let (result, _index, remaining) = futures::future::select_all(handles).await;
-            
-            if let Ok(Some((idx, data))) = result {
-                debug!("RPC {} responded successfully", idx);
-                results.push(data);
-                
-                // Early exit if we have enough responses
-                if results.len() >= threshold {
-                    debug!("Threshold met ({}/{}), canceling remaining requests", results.len(), threshold);
-                    break;
-                }
-            }
-            
-            handles = remaining;
-        }
-
-        results
-    }
-
-    pub fn client_count(&self) -> usize {
-        self.clients.len()
-    }
-
-    pub fn consensus_threshold(&self) -> usize {
-        self.consensus.threshold()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_multi_rpc_creation() {
-        let rpcs = vec![
-            "https://api.mainnet-beta.solana.com".to_string(),
-            "https://rpc.ankr.com/solana".to_string(),
-        ];
-
-        let client = MultiRpcClient::new(rpcs, 2);
-        assert_eq!(client.client_count(), 2);
-        assert_eq!(client.consensus_threshold(), 2);
-    }
-}
\ No newline at end of file
+use solana_client::client_error::{ClientError, ClientErrorKind, Result as ClientResult};
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcBlockConfig;
+use solana_client::rpc_response::RpcVoteAccountStatus;
+use solana_sdk::commitment_config::CommitmentConfig;
+use solana_sdk::signature::Signature;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, TransactionDetails, TransactionStatus,
+    UiConfirmedBlock, UiTransactionEncoding,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::{Id, JoinSet};
+use tracing::{debug, warn};
+
+use crate::error::{Result, StauroXError};
+use crate::types::{Commitment, SignatureConfirmation, SlotObservation};
+use super::consensus::{ConsensusEngine, ConsensusOutcome};
+use super::digest::transaction_digest;
+
+/// Raw responses gathered from a fan-out request
+struct FetchReport<T> {
+    responses: Vec<(String, T)>,
+    failed: Vec<String>,
+    latencies: HashMap<String, Duration>,
+}
+
+/// Timed requests in flight, one per RPC, remembering which task asks which endpoint
+struct Requests<T> {
+    tasks: JoinSet<(String, Duration, Result<T>)>,
+    endpoints: HashMap<Id, (String, Instant)>,
+}
+
+impl<T: 'static> Requests<T> {
+    /// Next finished request; a task that panicked fails with its endpoint's URL
+    async fn join_next(&mut self) -> Option<(String, Duration, Result<T>)> {
+        let joined = self.tasks.join_next_with_id().await?;
+        Some(match joined {
+            Ok((_, completed)) => completed,
+            Err(e) => {
+                let (url, started) = self.endpoints.remove(&e.id())?;
+                let error = ClientError::from(ClientErrorKind::Custom(format!(
+                    "Request task to {} failed: {}",
+                    url, e
+                )));
+                (url, started.elapsed(), Err(error.into()))
+            }
+        })
+    }
+
+    fn abort_all(&mut self) {
+        self.tasks.abort_all();
+    }
+}
+
+// getSignatureStatuses accepts at most this many signatures per call
+const MAX_SIGNATURE_STATUSES: usize = 256;
+
+/// Signature statuses gathered from every endpoint
+#[derive(Default)]
+struct SignatureStatuses {
+    /// Endpoints placing the signature in the expected slot
+    matching: Vec<(String, TransactionStatus)>,
+    /// Endpoints placing the signature in another slot, with that slot
+    moved: Vec<(String, u64)>,
+    /// Endpoints that don't know the signature
+    missing: Vec<String>,
+    failed: Vec<String>,
+}
+
+/// Whether a previously seen signature is still where it was
+#[derive(Debug)]
+pub enum SignatureRetention {
+    /// Still in the expected slot, at the agreed commitment
+    Retained(ConsensusOutcome<SignatureConfirmation>),
+    /// A quorum places it in this other slot, e.g. re-included on the surviving fork
+    Moved(u64),
+    /// A quorum no longer knows the signature
+    Vanished,
+}
+
+/// Multi-RPC client with consensus verification
+pub struct MultiRpcClient {
+    clients: Vec<Arc<RpcClient>>,
+    consensus: ConsensusEngine,
+    request_timeout: Duration,
+}
+
+impl MultiRpcClient {
+    pub fn new(rpc_urls: Vec<String>, consensus_threshold: usize, request_timeout: Duration) -> Self {
+        let clients: Vec<_> = rpc_urls
+            .into_iter()
+            .map(|url| Arc::new(RpcClient::new_with_timeout(url, request_timeout)))
+            .collect();
+
+        let consensus = ConsensusEngine::new(consensus_threshold, clients.len());
+
+        Self {
+            clients,
+            consensus,
+            request_timeout,
+        }
+    }
+
+    /// Assign trust weights per endpoint URL (unlisted endpoints weigh 1.0)
+    pub fn with_weights(mut self, weights: HashMap<String, f64>) -> Self {
+        self.consensus = self.consensus.with_weights(weights);
+        self
+    }
+
+    /// Require agreeing endpoints to hold this fraction of total weight
+    pub fn with_weighted_quorum(mut self, fraction: f64) -> Self {
+        self.consensus = self.consensus.with_weighted_quorum(fraction);
+        self
+    }
+
+    /// Fetch transaction from multiple RPCs with content-based consensus
+    ///
+    /// Responses are compared by their normalised digest; the majority
+    /// transaction is only returned once enough endpoints agree on it.
+    pub async fn fetch_transaction_with_consensus(
+        &self,
+        signature: &Signature,
+    ) -> Result<ConsensusOutcome<EncodedConfirmedTransactionWithStatusMeta>> {
+        let signature = *signature;
+
+        // Base64 is decoded into a VersionedTransaction by the parser
+        let config = solana_client::rpc_config::RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),  // CRITICAL: Support v0 transactions
+        };
+
+        // Every endpoint gets its full timeout so the agreement count is real
+        let report = self
+            .fetch_from_all_rpcs(
+                move |client| async move {
+                    client.get_transaction_with_config(&signature, config).await
+                },
+                transaction_digest,
+                false,
+            )
+            .await;
+
+        let mut outcome = self
+            .consensus
+            .find_consensus_by(report.responses, transaction_digest)?;
+        outcome.failed = report.failed;
+        outcome.latencies = report.latencies;
+
+        if !outcome.dissenting.is_empty() {
+            warn!(
+                "Transaction {} disputed by {} RPC(s): {:?}",
+                signature,
+                outcome.dissenting.len(),
+                outcome.dissenting
+            );
+        }
+
+        debug!(
+            "Transaction consensus: {}/{} RPCs agree ({} failed)",
+            outcome.agreeing.len(),
+            self.clients.len(),
+            outcome.failed.len()
+        );
+
+        Ok(outcome)
+    }
+
+    /// Get the commitment a signature has reached, agreed across endpoints
+    ///
+    /// Every endpoint is asked for the signature status (searching history).
+    /// The result is the highest commitment a quorum has reached; endpoints
+    /// that don't know the signature, or place it in another slot than
+    /// `slot`, count as dissenting.
+    pub async fn get_signature_status_with_consensus(
+        &self,
+        signature: &Signature,
+        slot: u64,
+    ) -> Result<ConsensusOutcome<SignatureConfirmation>> {
+        let statuses = self.collect_signature_status(signature, slot).await;
+        self.resolve_signature_status(signature, slot, statuses)
+    }
+
+    /// Batched `get_signature_status_with_consensus` for (signature, slot) pairs
+    ///
+    /// Each endpoint is asked once for the whole batch (chunked to the RPC
+    /// limit). Results line up with `signatures`.
+    pub async fn get_signature_statuses_with_consensus(
+        &self,
+        signatures: &[(Signature, u64)],
+    ) -> Vec<Result<ConsensusOutcome<SignatureConfirmation>>> {
+        let statuses = self.collect_signature_statuses(signatures).await;
+
+        signatures
+            .iter()
+            .zip(statuses)
+            .map(|((signature, slot), statuses)| {
+                self.resolve_signature_status(signature, *slot, statuses)
+            })
+            .collect()
+    }
+
+    /// Check whether a previously seen signature is still in `slot`
+    ///
+    /// Only a quorum of endpoints that no longer know the signature makes it
+    /// `Vanished`; a quorum agreeing on another slot makes it `Moved`.
+    /// Errors mean the answer is inconclusive, e.g. endpoints unreachable.
+    pub async fn check_signature_retained(
+        &self,
+        signature: &Signature,
+        slot: u64,
+    ) -> Result<SignatureRetention> {
+        let statuses = self.collect_signature_status(signature, slot).await;
+
+        if self.is_quorum(statuses.missing.iter()) {
+            warn!(
+                "{} no longer known according to {:?}",
+                signature, statuses.missing
+            );
+            return Ok(SignatureRetention::Vanished);
+        }
+
+        let mut new_slots: Vec<u64> = statuses.moved.iter().map(|(_, slot)| *slot).collect();
+        new_slots.sort_unstable();
+        new_slots.dedup();
+        for new_slot in new_slots {
+            let placing = statuses
+                .moved
+                .iter()
+                .filter(|(_, slot)| *slot == new_slot)
+                .map(|(url, _)| url);
+            if self.is_quorum(placing) {
+                warn!("{} moved from slot {} to {}", signature, slot, new_slot);
+                return Ok(SignatureRetention::Moved(new_slot));
+            }
+        }
+
+        self.resolve_signature_status(signature, slot, statuses)
+            .map(SignatureRetention::Retained)
+    }
+
+    /// Whether these endpoints alone reach the consensus threshold
+    fn is_quorum<'a>(&self, urls: impl Iterator<Item = &'a String>) -> bool {
+        let (count, weight) = urls.fold((0, 0.0), |(count, weight), url| {
+            (count + 1, weight + self.consensus.source_weight(url))
+        });
+        self.consensus.is_satisfied(count, weight)
+    }
+
+    /// Single-signature form of `collect_signature_statuses`
+    async fn collect_signature_status(&self, signature: &Signature, slot: u64) -> SignatureStatuses {
+        self.collect_signature_statuses(&[(*signature, slot)])
+            .await
+            .pop()
+            .unwrap_or_default()
+    }
+
+    /// Ask every endpoint for the signatures' statuses and sort the answers
+    /// per signature
+    async fn collect_signature_statuses(&self, signatures: &[(Signature, u64)]) -> Vec<SignatureStatuses> {
+        let batch: Arc<Vec<Signature>> =
+            Arc::new(signatures.iter().map(|(signature, _)| *signature).collect());
+
+        let results = self
+            .fetch_from_each_rpc(move |client| {
+                let batch = Arc::clone(&batch);
+                async move {
+                    let mut statuses = Vec::with_capacity(batch.len());
+                    for chunk in batch.chunks(MAX_SIGNATURE_STATUSES) {
+                        statuses.extend(client.get_signature_statuses_with_history(chunk).await?.value);
+                    }
+                    Ok(statuses)
+                }
+            })
+            .await;
+
+        let mut sorted: Vec<SignatureStatuses> =
+            signatures.iter().map(|_| SignatureStatuses::default()).collect();
+        for (url, result) in results {
+            let statuses = match result {
+                Ok(statuses) => statuses,
+                Err(e) => {
+                    debug!("Signature statuses unavailable from {}: {}", url, e);
+                    sorted.iter_mut().for_each(|entry| entry.failed.push(url.clone()));
+                    continue;
+                }
+            };
+
+            for (((signature, slot), entry), status) in signatures.iter().zip(&mut sorted).zip(statuses) {
+                match status {
+                    Some(status) if status.slot == *slot => entry.matching.push((url.clone(), status)),
+                    Some(status) => {
+                        warn!("RPC {} places {} in slot {} (expected {})", url, signature, status.slot, slot);
+                        entry.moved.push((url.clone(), status.slot));
+                    }
+                    None => entry.missing.push(url.clone()),
+                }
+            }
+        }
+
+        sorted
+    }
+
+    /// Agree on the highest commitment reached among matching statuses
+    fn resolve_signature_status(
+        &self,
+        signature: &Signature,
+        slot: u64,
+        statuses: SignatureStatuses,
+    ) -> Result<ConsensusOutcome<SignatureConfirmation>> {
+        let outcome = self
+            .consensus
+            .find_consensus_at_least(
+                statuses
+                    .matching
+                    .iter()
+                    .map(|(url, status)| (url.clone(), status))
+                    .collect(),
+                |status| Commitment::from(status.confirmation_status()),
+            )?;
+
+        // Rooted statuses carry no count, so they rank above any number
+        let confirmations = statuses
+            .matching
+            .iter()
+            .filter(|(url, _)| outcome.agreeing.contains(url))
+            .map(|(_, status)| status.confirmations)
+            .min_by_key(|confirmations| confirmations.unwrap_or(usize::MAX))
+            .flatten();
+
+        debug!(
+            "Signature {} reached {:?} on {}/{} RPCs",
+            signature,
+            outcome.value,
+            outcome.agreeing.len(),
+            self.clients.len()
+        );
+
+        Ok(ConsensusOutcome {
+            value: SignatureConfirmation {
+                slot,
+                commitment: outcome.value,
+                confirmations,
+            },
+            agreeing: outcome.agreeing,
+            dissenting: outcome
+                .dissenting
+                .into_iter()
+                .chain(statuses.moved.into_iter().map(|(url, _)| url))
+                .chain(statuses.missing)
+                .collect(),
+            failed: statuses.failed,
+            latencies: HashMap::new(),
+        })
+    }
+
+    /// Get current slot from multiple RPCs with consensus
+    pub async fn get_slot_with_consensus(&self) -> Result<u64> {
+        let report = self
+            .fetch_from_all_rpcs(|client| async move { client.get_slot().await }, |slot| *slot, true)
+            .await;
+
+        let outcome = self.consensus.find_consensus_by(report.responses, |slot| *slot)?;
+
+        debug!("Slot consensus achieved: {}", outcome.value);
+        Ok(outcome.value)
+    }
+
+    /// Observe each endpoint's confirmed tip, and the block every endpoint
+    /// holds at one common slot
+    ///
+    /// Tips are read at confirmed commitment, where forks are still visible.
+    /// Blocks are then fetched at the lowest tip, which every answering
+    /// endpoint has reached, so the health monitor can compare blockhash and
+    /// parent slot like for like. If the block can't be fetched (e.g. skipped
+    /// slot) the observation is still returned, just without block identity.
+    pub async fn observe_slot_per_endpoint(&self) -> Vec<(String, Result<SlotObservation>)> {
+        let tips = self
+            .fetch_from_each_rpc(|client| async move {
+                client.get_slot_with_commitment(CommitmentConfig::confirmed()).await
+            })
+            .await;
+
+        let mut blocks: HashMap<String, Result<UiConfirmedBlock>> = HashMap::new();
+        let common_slot = tips.iter().filter_map(|(_, tip)| tip.as_ref().ok()).min().copied();
+        if let Some(common_slot) = common_slot {
+            let block_config = RpcBlockConfig {
+                encoding: None,
+                transaction_details: Some(TransactionDetails::None),
+                rewards: Some(false),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            };
+            blocks = self
+                .fetch_from_each_rpc(move |client| async move {
+                    client.get_block_with_config(common_slot, block_config).await
+                })
+                .await
+                .into_iter()
+                .collect();
+        }
+
+        tips.into_iter()
+            .map(|(url, tip)| {
+                let observation = tip.map(|slot| {
+                    let observation = SlotObservation::new(slot, url.clone());
+                    match (common_slot, blocks.remove(&url)) {
+                        (Some(common_slot), Some(Ok(block))) => {
+                            observation.with_block(common_slot, block.blockhash, block.parent_slot)
+                        }
+                        (common_slot, Some(Err(e))) => {
+                            debug!("Block {:?} unavailable from {}: {}", common_slot, url, e);
+                            observation
+                        }
+                        _ => observation,
+                    }
+                });
+                (url, observation)
+            })
+            .collect()
+    }
+
+    /// Fetch the vote account set, preferring the most up-to-date endpoint
+    ///
+    /// Vote sets move every slot, so endpoints never agree byte-for-byte; the
+    /// response with the highest observed vote is taken instead. Votes are
+    /// read from the confirmed bank: the finalized one never holds votes past
+    /// an unrooted slot, which would grade every confirmed transaction as 0% stake.
+    pub async fn get_vote_accounts(&self) -> Result<RpcVoteAccountStatus> {
+        let responses = self
+            .fetch_from_each_rpc(|client| async move {
+                client
+                    .get_vote_accounts_with_commitment(CommitmentConfig::confirmed())
+                    .await
+            })
+            .await;
+
+        let mut freshest: Option<(u64, RpcVoteAccountStatus)> = None;
+        let mut last_error = None;
+        for (url, result) in responses {
+            match result {
+                Ok(status) => {
+                    let newest_vote = status
+                        .current
+                        .iter()
+                        .map(|account| account.last_vote)
+                        .max()
+                        .unwrap_or(0);
+                    if freshest.as_ref().is_none_or(|(best, _)| newest_vote > *best) {
+                        freshest = Some((newest_vote, status));
+                    }
+                }
+                Err(e) => {
+                    debug!("Vote accounts unavailable from {}: {}", url, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+
+        match (freshest, last_error) {
+            (Some((_, status)), _) => Ok(status),
+            (None, Some(e)) => Err(e),
+            (None, None) => Err(StauroXError::consensus_failure(0, 1, 0.0, None)),
+        }
+    }
+
+    /// Query the current slot from every endpoint on its own
+    ///
+    /// Used for per-source health observations, so no consensus is applied
+    /// and failures are reported per endpoint instead of being dropped.
+    pub async fn get_slot_per_endpoint(&self) -> Vec<(String, Result<u64>)> {
+        self.fetch_from_each_rpc(|client| async move { client.get_slot().await })
+            .await
+    }
+
+    /// Generic method to fetch from all RPCs in parallel
+    ///
+    /// Every request is bounded by the configured request timeout. Responses
+    /// are tagged with their endpoint URL and grouped by `key_fn`. With
+    /// `stop_at_quorum`, the remaining in-flight requests are aborted once any
+    /// group reaches the consensus threshold; otherwise every endpoint is
+    /// waited for, so the report shows how many actually agree.
+    async fn fetch_from_all_rpcs<T, K, F, Fut, KF>(
+        &self,
+        fetch_fn: F,
+        key_fn: KF,
+        stop_at_quorum: bool,
+    ) -> FetchReport<T>
+    where
+        T: Send + 'static,
+        K: Eq + Hash,
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = ClientResult<T>> + Send + 'static,
+        KF: Fn(&T) -> K,
+    {
+        let mut requests = self.spawn_requests(fetch_fn);
+
+        // Collect results as they come in, optionally stopping when enough agree
+        let mut report = FetchReport {
+            responses: vec![],
+            failed: vec![],
+            latencies: HashMap::new(),
+        };
+        let mut agreement: HashMap<K, (usize, f64)> = HashMap::new();
+
+        while let Some((url, latency, result)) = requests.join_next().await {
+            report.latencies.insert(url.clone(), latency);
+
+            match result {
+                Ok(data) => {
+                    debug!("RPC {} responded successfully in {:?}", url, latency);
+
+                    let (count, weight) = agreement.entry(key_fn(&data)).or_insert((0, 0.0));
+                    *count += 1;
+                    *weight += self.consensus.source_weight(&url);
+                    let (matching, matching_weight) = (*count, *weight);
+                    report.responses.push((url, data));
+
+                    // Early exit once the quorum agrees
+                    if stop_at_quorum && self.consensus.is_satisfied(matching, matching_weight) {
+                        debug!(
+                            "Quorum met ({}/{}, weight {:.2}), canceling remaining requests",
+                            matching,
+                            self.consensus.threshold(),
+                            matching_weight
+                        );
+                        requests.abort_all();
+                        break;
+                    }
+                }
+                Err(e) => {
+                    warn!("RPC {} failed: {}", url, e);
+                    report.failed.push(url);
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Fetch from every RPC independently, without consensus or early exit
+    async fn fetch_from_each_rpc<T, F, Fut>(&self, fetch_fn: F) -> Vec<(String, Result<T>)>
+    where
+        T: Send + 'static,
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = ClientResult<T>> + Send + 'static,
+    {
+        let mut requests = self.spawn_requests(fetch_fn);
+        let mut results = Vec::with_capacity(self.clients.len());
+
+        while let Some((url, _latency, result)) = requests.join_next().await {
+            results.push((url, result));
+        }
+
+        results
+    }
+
+    /// Spawn one timed request per RPC, tagged with its endpoint URL
+    fn spawn_requests<T, F, Fut>(&self, fetch_fn: F) -> Requests<T>
+    where
+        T: Send + 'static,
+        F: Fn(Arc<RpcClient>) -> Fut,
+        Fut: Future<Output = ClientResult<T>> + Send + 'static,
+    {
+        let mut requests = Requests {
+            tasks: JoinSet::new(),
+            endpoints: HashMap::new(),
+        };
+
+        for client in &self.clients {
+            let url = client.url();
+            let request = fetch_fn(Arc::clone(client));
+            let timeout = self.request_timeout;
+
+            let started = Instant::now();
+            let task = requests.tasks.spawn({
+                let url = url.clone();
+                async move {
+                    let result = match tokio::time::timeout(timeout, request).await {
+                        Ok(response) => response.map_err(StauroXError::from),
+                        Err(_) => Err(StauroXError::rpc_timeout(&url, timeout)),
+                    };
+                    (url, started.elapsed(), result)
+                }
+            });
+            requests.endpoints.insert(task.id(), (url, started));
+        }
+
+        requests
+    }
+
+    pub fn client_count(&self) -> usize {
+        self.clients.len()
+    }
+
+    pub fn consensus_threshold(&self) -> usize {
+        self.consensus.threshold()
+    }
+
+    pub fn required_weight(&self) -> Option<f64> {
+        self.consensus.required_weight()
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use solana_sdk::pubkey::Pubkey;
+
+    /// Mock RPC server that answers the client's cluster version query
+    pub(crate) async fn mock_server() -> mockito::ServerGuard {
+        let mut server = mockito::Server::new_async().await;
+        mock_rpc(&mut server, "getVersion", json!({ "solana-core": "1.18.26" })).await;
+        server
+    }
+
+    /// Mock JSON-RPC endpoint answering every `method` call with `result`
+    pub(crate) async fn mock_rpc(
+        server: &mut mockito::ServerGuard,
+        method: &str,
+        result: Value,
+    ) -> mockito::Mock {
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({ "method": method })))
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string())
+            .create_async()
+            .await
+    }
+
+    /// `getSignatureStatuses` result placing one signature in `slot`
+    pub(crate) fn signature_status(slot: u64, commitment: &str) -> Value {
+        let confirmations = if commitment == "finalized" { Value::Null } else { json!(10) };
+        json!({
+            "context": { "slot": slot + 40 },
+            "value": [{
+                "slot": slot,
+                "confirmations": confirmations,
+                "err": null,
+                "status": { "Ok": null },
+                "confirmationStatus": commitment
+            }]
+        })
+    }
+
+    #[test]
+    fn test_multi_rpc_creation() {
+        let rpcs = vec![
+            "https://api.mainnet-beta.solana.com".to_string(),
+            "https://rpc.ankr.com/solana".to_string(),
+        ];
+
+        let client = MultiRpcClient::new(rpcs, 2, Duration::from_millis(5000));
+        assert_eq!(client.client_count(), 2);
+        assert_eq!(client.consensus_threshold(), 2);
+        assert_eq!(client.request_timeout(), Duration::from_millis(5000));
+        assert_eq!(client.required_weight(), None);
+    }
+
+    #[test]
+    fn test_multi_rpc_weighted_quorum() {
+        let rpcs = vec![
+            "https://api.mainnet-beta.solana.com".to_string(),
+            "https://rpc.ankr.com/solana".to_string(),
+        ];
+        let weights = HashMap::from([("https://api.mainnet-beta.solana.com".to_string(), 3.0)]);
+
+        let client = MultiRpcClient::new(rpcs, 1, Duration::from_millis(5000))
+            .with_weights(weights)
+            .with_weighted_quorum(0.5);
+
+        assert_eq!(client.required_weight(), Some(2.0));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_rpcs_time_out() {
+        // Non-routable address: the request hangs until the timeout fires
+        let rpcs = vec!["http://10.255.255.1:8899".to_string()];
+        let client = MultiRpcClient::new(rpcs, 1, Duration::from_millis(100));
+
+        let started = std::time::Instant::now();
+        let result = client.get_slot_with_consensus().await;
+
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_slot_per_endpoint_reports_failures() {
+        let rpcs = vec![
+            "http://10.255.255.1:8899".to_string(),
+            "http://10.255.255.2:8899".to_string(),
+        ];
+        let client = MultiRpcClient::new(rpcs, 1, Duration::from_millis(100));
+
+        let slots = client.get_slot_per_endpoint().await;
+
+        assert_eq!(slots.len(), 2);
+        assert!(slots.iter().all(|(_, result)| result.is_err()));
+    }
+
+    #[tokio::test]
+    async fn test_panicked_requests_report_their_endpoint() {
+        let broken = "http://10.255.255.1:8899".to_string();
+        let rpcs = vec![broken.clone(), "http://10.255.255.2:8899".to_string()];
+        let client = MultiRpcClient::new(rpcs, 1, Duration::from_millis(100));
+        let fetch = |rpc: Arc<RpcClient>| async move {
+            assert!(!rpc.url().ends_with(".1:8899"), "request task panicked");
+            Ok(7u64)
+        };
+
+        let report = client.fetch_from_all_rpcs(fetch, |slot| *slot, false).await;
+        assert_eq!(report.failed, [broken.as_str()]);
+        assert_eq!(report.responses.len(), 1);
+
+        let results = client.fetch_from_each_rpc(fetch).await;
+        let (url, result) = results.iter().find(|(_, result)| result.is_err()).unwrap();
+        assert_eq!(*url, broken);
+        assert!(result.as_ref().unwrap_err().to_string().contains(&broken));
+    }
+
+    #[tokio::test]
+    async fn test_vote_accounts_read_from_confirmed_bank() {
+        let mut server = mock_server().await;
+        let vote_accounts = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::PartialJson(json!({ "method": "getVoteAccounts" })),
+                mockito::Matcher::Regex(r#""commitment":"confirmed""#.to_string()),
+            ]))
+            .with_header("content-type", "application/json")
+            .with_body(
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": {
+                        "current": [{
+                            "votePubkey": Pubkey::new_unique().to_string(),
+                            "nodePubkey": Pubkey::new_unique().to_string(),
+                            "activatedStake": 1_000,
+                            "epochVoteAccount": true,
+                            "commission": 0,
+                            "lastVote": 5_000,
+                            "epochCredits": [],
+                            "rootSlot": 4_968
+                        }],
+                        "delinquent": []
+                    }
+                })
+                .to_string(),
+            )
+            .create_async()
+            .await;
+
+        let client = MultiRpcClient::new(vec![server.url()], 1, Duration::from_secs(2));
+        let status = client.get_vote_accounts().await.unwrap();
+
+        assert_eq!(status.current[0].last_vote, 5_000);
+        vote_accounts.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_blocks_observed_at_lowest_confirmed_tip() {
+        let mut servers = Vec::new();
+        for (tip, blockhash) in [(105, "hashAhead"), (100, "hashBehind")] {
+            let mut server = mock_server().await;
+            server
+                .mock("POST", "/")
+                .match_body(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::PartialJson(json!({ "method": "getSlot" })),
+                    mockito::Matcher::Regex(r#""commitment":"confirmed""#.to_string()),
+                ]))
+                .with_body(json!({ "jsonrpc": "2.0", "id": 1, "result": tip }).to_string())
+                .create_async()
+                .await;
+            server
+                .mock("POST", "/")
+                .match_body(mockito::Matcher::AllOf(vec![
+                    mockito::Matcher::PartialJson(json!({ "method": "getBlock", "params": [100] })),
+                    mockito::Matcher::Regex(r#""commitment":"confirmed""#.to_string()),
+                ]))
+                .with_body(
+                    json!({
+                        "jsonrpc": "2.0",
+                        "id": 1,
+                        "result": {
+                            "previousBlockhash": "hashParent",
+                            "blockhash": blockhash,
+                            "parentSlot": 99,
+                            "blockTime": null,
+                            "blockHeight": null
+                        }
+                    })
+                    .to_string(),
+                )
+                .create_async()
+                .await;
+            servers.push(server);
+        }
+
+        let urls = servers.iter().map(|server| server.url()).collect();
+        let client = MultiRpcClient::new(urls, 1, Duration::from_secs(2));
+        let mut observations: Vec<SlotObservation> = client
+            .observe_slot_per_endpoint()
+            .await
+            .into_iter()
+            .map(|(_, observation)| observation.unwrap())
+            .collect();
+        observations.sort_by_key(|observation| observation.slot);
+
+        let observed: Vec<_> = observations
+            .iter()
+            .map(|obs| (obs.slot, obs.block_slot, obs.blockhash.as_deref()))
+            .collect();
+        assert_eq!(
+            observed,
+            [(100, Some(100), Some("hashBehind")), (105, Some(100), Some("hashAhead"))]
+        );
+    }
+}
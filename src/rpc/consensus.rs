@@ -1,121 +1,436 @@
-use std::collections::HashMap;
-use std::fmt::Debug;
-use std::hash::Hash;
-
-use crate::error::{Result, StauroXError};
-
-/// Generic consensus engine - works with any type
-pub struct ConsensusEngine {
-    threshold: usize,
-    _total_sources: usize,
-}
-
-impl ConsensusEngine {
-    pub fn new(threshold: usize, _total_sources: usize) -> Self {
-        Self {
-            threshold,
-            _total_sources,
-        }
-    }
-
-    /// Get consensus threshold
-    pub fn threshold(&self) -> usize {
-        self.threshold
-    }
-
-    /// Check if we have enough responses
-    pub fn has_minimum_responses<T>(&self, responses: &[T]) -> Result<()> {
-        if responses.len() < self.threshold {
-            return Err(StauroXError::consensus_failure(
-                responses.len(),
-                self.threshold,
-            ));
-        }
-        Ok(())
-    }
-
-    /// Find consensus value from responses (simple majority)
-    pub fn find_consensus<T>(&self, responses: Vec<T>) -> Result<T>
-    where
-        T: Eq + Hash + Clone + Debug,
-    {
-        self.has_minimum_responses(&responses)?;
-
-        let mut counts: HashMap<T, usize> = HashMap::new();
-        for response in &responses {
-            *counts.entry(response.clone()).or_insert(0) += 1;
-        }
-
-        // Find the most common value
-        let (consensus_value, count) = counts
-            .into_iter()
-            .max_by_key(|(_, count)| *count)
-            .ok_or_else(|| StauroXError::consensus_failure(0, self.threshold))?;
-
-        // Verify it meets threshold
-        if count < self.threshold {
-            return Err(StauroXError::consensus_failure(count, self.threshold));
-        }
-
-        Ok(consensus_value)
-    }
-
-    /// Calculate consensus ratio (for metrics)
-    pub fn consensus_ratio<T>(&self, responses: &[T]) -> f64
-    where
-        T: Eq + Hash,
-    {
-        if responses.is_empty() {
-            return 0.0;
-        }
-
-        let mut counts: HashMap<&T, usize> = HashMap::new();
-        for response in responses {
-            *counts.entry(response).or_insert(0) += 1;
-        }
-
-        let max_count = *counts.values().max().unwrap_or(&0);
-        max_count as f64 / responses.len() as f64
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_find_consensus_success() {
-        let engine = ConsensusEngine::new(3, 4);
-        let responses = vec![100u64, 100, 100, 101];
-        
-        let result = engine.find_consensus(responses);
-        assert_eq!(result.unwrap(), 100);
-    }
-
-    #[test]
-    fn test_find_consensus_failure() {
-        let engine = ConsensusEngine::new(3, 4);
-        let responses = vec![100u64, 101, 102, 103];
-        
-        let result = engine.find_consensus(responses);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_insufficient_responses() {
-        let engine = ConsensusEngine::new(3, 4);
-        let responses = vec![100u64, 100];
-        
-        let result = engine.find_consensus(responses);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_consensus_ratio() {
-        let engine = ConsensusEngine::new(3, 4);
-        let responses = vec![100u64, 100, 100, 101];
-        
-        let ratio = engine.consensus_ratio(&responses);
-        assert_eq!(ratio, 0.75);
-    }
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::time::Duration;
+
+use crate::error::{Result, StauroXError};
+
+/// Agreed value plus the sources that did and didn't back it
+#[derive(Debug, Clone)]
+pub struct ConsensusOutcome<T> {
+    pub value: T,
+    pub agreeing: Vec<String>,
+    pub dissenting: Vec<String>,
+    /// Sources that errored or timed out (filled in by the RPC layer)
+    pub failed: Vec<String>,
+    /// Response latency per source, including failed ones
+    pub latencies: HashMap<String, Duration>,
+}
+
+/// Weight of a source with no configured trust weight
+pub const DEFAULT_SOURCE_WEIGHT: f64 = 1.0;
+
+// Slack for float rounding when comparing against the weighted quorum
+const WEIGHT_EPSILON: f64 = 1e-9;
+
+/// Generic consensus engine - works with any type
+pub struct ConsensusEngine {
+    threshold: usize,
+    total_sources: usize,
+    weights: HashMap<String, f64>,
+    quorum_fraction: Option<f64>,
+}
+
+impl ConsensusEngine {
+    pub fn new(threshold: usize, total_sources: usize) -> Self {
+        Self {
+            threshold,
+            total_sources,
+            weights: HashMap::new(),
+            quorum_fraction: None,
+        }
+    }
+
+    /// Assign trust weights per source
+    pub fn with_weights(mut self, weights: HashMap<String, f64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Additionally require agreeing sources to hold this fraction of total weight
+    pub fn with_weighted_quorum(mut self, fraction: f64) -> Self {
+        self.quorum_fraction = Some(fraction);
+        self
+    }
+
+    /// Get consensus threshold
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn source_weight(&self, source: &str) -> f64 {
+        self.weights
+            .get(source)
+            .copied()
+            .unwrap_or(DEFAULT_SOURCE_WEIGHT)
+    }
+
+    /// Total weight of all configured sources
+    pub fn total_weight(&self) -> f64 {
+        let listed: f64 = self.weights.values().sum();
+        let unlisted = self.total_sources.saturating_sub(self.weights.len());
+        listed + unlisted as f64 * DEFAULT_SOURCE_WEIGHT
+    }
+
+    /// Weight agreeing sources must reach, if a weighted quorum is configured
+    pub fn required_weight(&self) -> Option<f64> {
+        self.quorum_fraction
+            .map(|fraction| fraction * self.total_weight())
+    }
+
+    /// Whether a group of agreeing sources satisfies the count and weight quorum
+    pub fn is_satisfied(&self, count: usize, weight: f64) -> bool {
+        count >= self.threshold
+            && self
+                .required_weight()
+                .is_none_or(|required| weight + WEIGHT_EPSILON >= required)
+    }
+
+    /// Check if we have enough responses
+    ///
+    /// Unsourced responses carry no weights, so each counts as a default-weight source.
+    pub fn has_minimum_responses<T>(&self, responses: &[T]) -> Result<()> {
+        if responses.len() < self.threshold {
+            return Err(StauroXError::consensus_failure(
+                responses.len(),
+                self.threshold,
+                responses.len() as f64 * DEFAULT_SOURCE_WEIGHT,
+                None,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check that the responding sources could reach the quorum if they all agreed
+    pub fn has_minimum_sourced_responses<T>(&self, responses: &[(String, T)]) -> Result<()> {
+        let weight: f64 = responses
+            .iter()
+            .map(|(source, _)| self.source_weight(source))
+            .sum();
+
+        if !self.is_satisfied(responses.len(), weight) {
+            return Err(StauroXError::consensus_failure(
+                responses.len(),
+                self.threshold,
+                weight,
+                self.required_weight(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Find consensus value from responses (simple majority)
+    pub fn find_consensus<T>(&self, responses: Vec<T>) -> Result<T>
+    where
+        T: Eq + Hash + Clone + Debug,
+    {
+        self.has_minimum_responses(&responses)?;
+
+        let mut counts: HashMap<T, usize> = HashMap::new();
+        for response in &responses {
+            *counts.entry(response.clone()).or_insert(0) += 1;
+        }
+
+        // Find the most common value
+        let (consensus_value, count) = counts
+            .into_iter()
+            .max_by_key(|(_, count)| *count)
+            .ok_or_else(|| StauroXError::consensus_failure(0, self.threshold, 0.0, None))?;
+
+        // Verify it meets threshold
+        if count < self.threshold {
+            let weight = count as f64 * DEFAULT_SOURCE_WEIGHT;
+            return Err(StauroXError::consensus_failure(count, self.threshold, weight, None));
+        }
+
+        Ok(consensus_value)
+    }
+
+    /// Find consensus over sourced responses, comparing them by a derived key
+    ///
+    /// Responses whose keys match are treated as identical. The group with
+    /// the most trust weight wins if it meets the count threshold and any
+    /// weighted quorum; every other source is reported as dissenting.
+    pub fn find_consensus_by<T, K, F>(
+        &self,
+        responses: Vec<(String, T)>,
+        key_fn: F,
+    ) -> Result<ConsensusOutcome<T>>
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        self.has_minimum_sourced_responses(&responses)?;
+
+        // Group sources by key, remembering one representative value per group
+        let mut groups: Vec<(K, T, Vec<String>, f64)> = Vec::new();
+        for (source, response) in responses {
+            let key = key_fn(&response);
+            let weight = self.source_weight(&source);
+            match groups.iter_mut().find(|(k, _, _, _)| *k == key) {
+                Some((_, _, sources, total)) => {
+                    sources.push(source);
+                    *total += weight;
+                }
+                None => groups.push((key, response, vec![source], weight)),
+            }
+        }
+
+        // Heaviest group wins; ties go to the group that answered first
+        let winner = groups
+            .iter()
+            .enumerate()
+            .max_by(|(ia, a), (ib, b)| a.3.total_cmp(&b.3).then(ib.cmp(ia)))
+            .map(|(idx, _)| idx)
+            .ok_or_else(|| {
+                StauroXError::consensus_failure(0, self.threshold, 0.0, self.required_weight())
+            })?;
+
+        let (_, value, agreeing, weight) = groups.remove(winner);
+        let dissenting: Vec<String> = groups
+            .into_iter()
+            .flat_map(|(_, _, sources, _)| sources)
+            .collect();
+
+        if !self.is_satisfied(agreeing.len(), weight) {
+            return Err(StauroXError::consensus_disagreement(
+                agreeing.len(),
+                self.threshold,
+                weight,
+                self.required_weight(),
+                dissenting,
+            ));
+        }
+
+        Ok(ConsensusOutcome {
+            value,
+            agreeing,
+            dissenting,
+            failed: Vec::new(),
+            latencies: HashMap::new(),
+        })
+    }
+
+    /// Find the highest level a quorum of sources reports reaching
+    ///
+    /// For monotonic responses (e.g. commitment), a source at a higher level
+    /// also backs every lower one. Sources below the agreed level are
+    /// reported as dissenting.
+    pub fn find_consensus_at_least<T, L, F>(
+        &self,
+        responses: Vec<(String, T)>,
+        level_fn: F,
+    ) -> Result<ConsensusOutcome<L>>
+    where
+        L: Ord + Copy,
+        F: Fn(&T) -> L,
+    {
+        self.has_minimum_sourced_responses(&responses)?;
+
+        let levels: Vec<(String, L)> = responses
+            .iter()
+            .map(|(source, response)| (source.clone(), level_fn(response)))
+            .collect();
+
+        let mut candidates: Vec<L> = levels.iter().map(|(_, level)| *level).collect();
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+        candidates.dedup();
+
+        // Try the highest level first; the lowest is backed by every source
+        let mut best = (0, 0.0);
+        for candidate in candidates {
+            let (agreeing, dissenting): (Vec<_>, Vec<_>) = levels
+                .iter()
+                .partition(|(_, level)| *level >= candidate);
+            let weight: f64 = agreeing
+                .iter()
+                .map(|(source, _)| self.source_weight(source))
+                .sum();
+
+            if self.is_satisfied(agreeing.len(), weight) {
+                return Ok(ConsensusOutcome {
+                    value: candidate,
+                    agreeing: agreeing.into_iter().map(|(source, _)| source.clone()).collect(),
+                    dissenting: dissenting.into_iter().map(|(source, _)| source.clone()).collect(),
+                    failed: Vec::new(),
+                    latencies: HashMap::new(),
+                });
+            }
+            best = (agreeing.len(), weight);
+        }
+
+        Err(StauroXError::consensus_disagreement(
+            best.0,
+            self.threshold,
+            best.1,
+            self.required_weight(),
+            Vec::new(),
+        ))
+    }
+
+    /// Calculate consensus ratio (for metrics)
+    pub fn consensus_ratio<T>(&self, responses: &[T]) -> f64
+    where
+        T: Eq + Hash,
+    {
+        if responses.is_empty() {
+            return 0.0;
+        }
+
+        let mut counts: HashMap<&T, usize> = HashMap::new();
+        for response in responses {
+            *counts.entry(response).or_insert(0) += 1;
+        }
+
+        let max_count = *counts.values().max().unwrap_or(&0);
+        max_count as f64 / responses.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_consensus_success() {
+        let engine = ConsensusEngine::new(3, 4);
+        let responses = vec![100u64, 100, 100, 101];
+        
+        let result = engine.find_consensus(responses);
+        assert_eq!(result.unwrap(), 100);
+    }
+
+    #[test]
+    fn test_find_consensus_failure() {
+        let engine = ConsensusEngine::new(3, 4);
+        let responses = vec![100u64, 101, 102, 103];
+        
+        let result = engine.find_consensus(responses);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_insufficient_responses() {
+        let engine = ConsensusEngine::new(3, 4);
+        let responses = vec![100u64, 100];
+        
+        let result = engine.find_consensus(responses);
+        assert!(result.is_err());
+    }
+
+    fn sourced(values: &[u64]) -> Vec<(String, u64)> {
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| (format!("rpc{}", i), *v))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_consensus_by_reports_dissenters() {
+        let engine = ConsensusEngine::new(3, 4);
+
+        let outcome = engine
+            .find_consensus_by(sourced(&[100, 100, 101, 100]), |v| *v)
+            .unwrap();
+
+        assert_eq!(outcome.value, 100);
+        assert_eq!(outcome.agreeing, vec!["rpc0", "rpc1", "rpc3"]);
+        assert_eq!(outcome.dissenting, vec!["rpc2"]);
+    }
+
+    #[test]
+    fn test_find_consensus_by_disagreement() {
+        let engine = ConsensusEngine::new(3, 4);
+
+        let result = engine.find_consensus_by(sourced(&[100, 100, 101, 102]), |v| *v);
+
+        match result {
+            Err(StauroXError::ConsensusFailure { responses, required, dissenting, .. }) => {
+                assert_eq!(responses, 2);
+                assert_eq!(required, 3);
+                assert_eq!(dissenting, vec!["rpc2", "rpc3"]);
+            }
+            other => panic!("expected consensus failure, got {:?}", other.map(|o| o.value)),
+        }
+    }
+
+    fn trusted_rpc0() -> HashMap<String, f64> {
+        HashMap::from([("rpc0".to_string(), 3.0)])
+    }
+
+    #[test]
+    fn test_weighted_quorum_trusted_source_prevails() {
+        // rpc0 weighs 3, the rest 1 each: total 6, quorum 2/3 needs 4
+        let engine = ConsensusEngine::new(1, 4)
+            .with_weights(trusted_rpc0())
+            .with_weighted_quorum(2.0 / 3.0);
+
+        let outcome = engine
+            .find_consensus_by(sourced(&[100, 100, 101, 101]), |v| *v)
+            .unwrap();
+
+        assert_eq!(engine.total_weight(), 6.0);
+        assert_eq!(outcome.value, 100);
+        assert_eq!(outcome.dissenting, vec!["rpc2", "rpc3"]);
+    }
+
+    #[test]
+    fn test_weighted_quorum_failure_reports_weights() {
+        let engine = ConsensusEngine::new(1, 4)
+            .with_weights(trusted_rpc0())
+            .with_weighted_quorum(2.0 / 3.0);
+
+        let result = engine.find_consensus_by(sourced(&[100, 101, 101, 101]), |v| *v);
+
+        match result {
+            Err(StauroXError::ConsensusFailure { weight, required_weight, .. }) => {
+                assert_eq!(weight, 3.0);
+                assert!((required_weight.unwrap() - 4.0).abs() < 1e-9);
+            }
+            other => panic!("expected consensus failure, got {:?}", other.map(|o| o.value)),
+        }
+    }
+
+    #[test]
+    fn test_too_few_responses_report_weights() {
+        let engine = ConsensusEngine::new(2, 4)
+            .with_weights(trusted_rpc0())
+            .with_weighted_quorum(2.0 / 3.0);
+
+        // Only rpc0 answered: enough weight to matter, but short on both counts
+        let result = engine.find_consensus_by(sourced(&[100]), |v| *v);
+
+        match result {
+            Err(StauroXError::ConsensusFailure { responses, weight, required_weight, .. }) => {
+                assert_eq!(responses, 1);
+                assert_eq!(weight, 3.0);
+                assert!((required_weight.unwrap() - 4.0).abs() < 1e-9);
+            }
+            other => panic!("expected consensus failure, got {:?}", other.map(|o| o.value)),
+        }
+    }
+
+    #[test]
+    fn test_find_consensus_at_least() {
+        let engine = ConsensusEngine::new(3, 4);
+
+        // Two sources at 3, one at 2: three of them have reached at least 2
+        let outcome = engine
+            .find_consensus_at_least(sourced(&[3, 2, 1, 3]), |v| *v)
+            .unwrap();
+
+        assert_eq!(outcome.value, 2);
+        assert_eq!(outcome.agreeing, vec!["rpc0", "rpc1", "rpc3"]);
+        assert_eq!(outcome.dissenting, vec!["rpc2"]);
+    }
+
+    #[test]
+    fn test_consensus_ratio() {
+        let engine = ConsensusEngine::new(3, 4);
+        let responses = vec![100u64, 100, 100, 101];
+        
+        let ratio = engine.consensus_ratio(&responses);
+        assert_eq!(ratio, 0.75);
+    }
 }
\ No newline at end of file
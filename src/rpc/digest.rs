@@ -0,0 +1,382 @@
+use sha2::{Digest, Sha256};
+use solana_sdk::message::MessageHeader;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{
+    EncodedConfirmedTransactionWithStatusMeta, EncodedTransaction, UiInnerInstructions,
+    UiInstruction, UiLoadedAddresses, UiMessage, UiTransactionTokenBalance,
+};
+
+/// Normalised fingerprint of a transaction response, used to compare RPC answers
+pub type TransactionDigest = [u8; 32];
+
+/// Hash the consensus-relevant content of a transaction response
+///
+/// Covers slot, the message (header, account keys, instructions and lookup
+/// tables), execution status, CPIs, the addresses loaded from lookup tables,
+/// and pre/post lamport and token balances: everything the parser reads from
+/// the representative response. Fields that legitimately
+/// differ between honest RPCs (block time, log truncation, rewards) are left out.
+/// Binary encodings are decoded and hashed as their JSON form would be, so
+/// the digest does not depend on the encoding.
+pub fn transaction_digest(tx: &EncodedConfirmedTransactionWithStatusMeta) -> TransactionDigest {
+    let mut hasher = Sha256::new();
+
+    hasher.update(tx.slot.to_le_bytes());
+
+    match &tx.transaction.transaction {
+        EncodedTransaction::Json(ui_tx) => match &ui_tx.message {
+            UiMessage::Raw(msg) => {
+                hash_header(&mut hasher, &msg.header);
+
+                hash_len(&mut hasher, msg.account_keys.len());
+                for key in &msg.account_keys {
+                    hash_bytes(&mut hasher, key.as_bytes());
+                }
+
+                hash_len(&mut hasher, msg.instructions.len());
+                for ix in &msg.instructions {
+                    hash_instruction(&mut hasher, ix.program_id_index, &ix.accounts, &ix.data);
+                }
+
+                let lookups = msg.address_table_lookups.as_deref().unwrap_or_default();
+                hash_len(&mut hasher, lookups.len());
+                for lookup in lookups {
+                    hash_lookup(
+                        &mut hasher,
+                        &lookup.account_key,
+                        &lookup.writable_indexes,
+                        &lookup.readonly_indexes,
+                    );
+                }
+            }
+            UiMessage::Parsed(msg) => {
+                hash_bytes(&mut hasher, format!("{:?}", msg.instructions).as_bytes());
+            }
+        },
+        encoded => match encoded.decode() {
+            Some(versioned) => {
+                let message = &versioned.message;
+                hash_header(&mut hasher, message.header());
+
+                let account_keys = message.static_account_keys();
+                hash_len(&mut hasher, account_keys.len());
+                for key in account_keys {
+                    hash_bytes(&mut hasher, key.to_string().as_bytes());
+                }
+
+                hash_len(&mut hasher, message.instructions().len());
+                for ix in message.instructions() {
+                    let data = bs58::encode(&ix.data).into_string();
+                    hash_instruction(&mut hasher, ix.program_id_index, &ix.accounts, &data);
+                }
+
+                let lookups = message.address_table_lookups().unwrap_or_default();
+                hash_len(&mut hasher, lookups.len());
+                for lookup in lookups {
+                    hash_lookup(
+                        &mut hasher,
+                        &lookup.account_key.to_string(),
+                        &lookup.writable_indexes,
+                        &lookup.readonly_indexes,
+                    );
+                }
+            }
+            None => {
+                hash_bytes(&mut hasher, format!("{:?}", encoded).as_bytes());
+            }
+        },
+    }
+
+    match &tx.transaction.meta {
+        Some(meta) => {
+            hasher.update([1]);
+            hash_bytes(&mut hasher, format!("{:?}", meta.err).as_bytes());
+            hash_balances(&mut hasher, &meta.pre_balances);
+            hash_balances(&mut hasher, &meta.post_balances);
+            hash_inner_instructions(&mut hasher, &meta.inner_instructions);
+            hash_loaded_addresses(&mut hasher, &meta.loaded_addresses);
+            hash_token_balances(&mut hasher, &meta.pre_token_balances);
+            hash_token_balances(&mut hasher, &meta.post_token_balances);
+        }
+        None => hasher.update([0]),
+    }
+
+    hasher.finalize().into()
+}
+
+fn hash_header(hasher: &mut Sha256, header: &MessageHeader) {
+    hasher.update([
+        header.num_required_signatures,
+        header.num_readonly_signed_accounts,
+        header.num_readonly_unsigned_accounts,
+    ]);
+}
+
+/// One compiled instruction, with its data in base58 as JSON responses carry it
+fn hash_instruction(hasher: &mut Sha256, program_id_index: u8, accounts: &[u8], data: &str) {
+    hasher.update([program_id_index]);
+    hash_bytes(hasher, accounts);
+    hash_bytes(hasher, data.as_bytes());
+}
+
+fn hash_lookup(hasher: &mut Sha256, table: &str, writable: &[u8], readonly: &[u8]) {
+    hash_bytes(hasher, table.as_bytes());
+    hash_bytes(hasher, writable);
+    hash_bytes(hasher, readonly);
+}
+
+/// Lookup-table keys in the order instructions index them: writable, then readonly
+fn hash_loaded_addresses(hasher: &mut Sha256, loaded: &OptionSerializer<UiLoadedAddresses>) {
+    let OptionSerializer::Some(loaded) = loaded else {
+        hasher.update([0]);
+        return;
+    };
+
+    hasher.update([1]);
+    for keys in [&loaded.writable, &loaded.readonly] {
+        hash_len(hasher, keys.len());
+        for key in keys {
+            hash_bytes(hasher, key.as_bytes());
+        }
+    }
+}
+
+fn hash_inner_instructions(
+    hasher: &mut Sha256,
+    inner_instructions: &OptionSerializer<Vec<UiInnerInstructions>>,
+) {
+    let OptionSerializer::Some(inner_instructions) = inner_instructions else {
+        hasher.update([0]);
+        return;
+    };
+
+    hasher.update([1]);
+    hash_len(hasher, inner_instructions.len());
+    for inner in inner_instructions {
+        hasher.update([inner.index]);
+        hash_len(hasher, inner.instructions.len());
+        for ix in &inner.instructions {
+            match ix {
+                UiInstruction::Compiled(ix) => {
+                    hash_instruction(hasher, ix.program_id_index, &ix.accounts, &ix.data);
+                }
+                UiInstruction::Parsed(ix) => {
+                    hash_bytes(hasher, format!("{:?}", ix).as_bytes());
+                }
+            }
+        }
+    }
+}
+
+fn hash_token_balances(
+    hasher: &mut Sha256,
+    balances: &OptionSerializer<Vec<UiTransactionTokenBalance>>,
+) {
+    let OptionSerializer::Some(balances) = balances else {
+        hasher.update([0]);
+        return;
+    };
+
+    hasher.update([1]);
+    hash_len(hasher, balances.len());
+    for balance in balances {
+        hasher.update([balance.account_index]);
+        hash_bytes(hasher, balance.mint.as_bytes());
+        hash_bytes(hasher, balance.ui_token_amount.amount.as_bytes());
+        hasher.update([balance.ui_token_amount.decimals]);
+    }
+}
+
+fn hash_len(hasher: &mut Sha256, len: usize) {
+    hasher.update((len as u64).to_le_bytes());
+}
+
+// Length-prefixed so adjacent fields can't be shifted into each other
+fn hash_bytes(hasher: &mut Sha256, bytes: &[u8]) {
+    hash_len(hasher, bytes.len());
+    hasher.update(bytes);
+}
+
+fn hash_balances(hasher: &mut Sha256, balances: &[u64]) {
+    hash_len(hasher, balances.len());
+    for balance in balances {
+        hasher.update(balance.to_le_bytes());
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use solana_sdk::hash::Hash;
+    use solana_sdk::instruction::CompiledInstruction;
+    use solana_sdk::message::{Message, VersionedMessage};
+    use solana_sdk::signature::Signature;
+    use solana_sdk::transaction::VersionedTransaction;
+    use solana_transaction_status::{
+        EncodableWithMeta, EncodedTransactionWithStatusMeta, TransactionStatusMeta,
+        UiAddressTableLookup, UiCompiledInstruction, UiTransactionEncoding,
+    };
+
+    /// Minimal JSON-encoded transaction response for tests
+    pub(crate) fn sample_transaction(
+        slot: u64,
+        data: &str,
+        post_balance: u64,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        serde_json::from_value(serde_json::json!({
+            "slot": slot,
+            "blockTime": 1_700_000_000,
+            "transaction": {
+                "signatures": ["1111111111111111111111111111111111111111111111111111111111111111"],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 1
+                    },
+                    "accountKeys": [
+                        "11111111111111111111111111111111",
+                        "wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb"
+                    ],
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [
+                        { "programIdIndex": 1, "accounts": [0], "data": data, "stackHeight": null }
+                    ]
+                }
+            },
+            "meta": {
+                "err": null,
+                "status": { "Ok": null },
+                "fee": 5000,
+                "preBalances": [1_000_000, 1],
+                "postBalances": [post_balance, 1],
+                "innerInstructions": [],
+                "logMessages": [],
+                "preTokenBalances": [],
+                "postTokenBalances": [],
+                "rewards": []
+            }
+        }))
+        .expect("valid transaction fixture")
+    }
+
+    #[test]
+    fn test_identical_responses_share_digest() {
+        let a = sample_transaction(100, "2", 995_000);
+        let mut b = sample_transaction(100, "2", 995_000);
+        b.block_time = Some(1_700_000_123);
+
+        assert_eq!(transaction_digest(&a), transaction_digest(&b));
+    }
+
+    #[test]
+    fn test_content_changes_alter_digest() {
+        let base = transaction_digest(&sample_transaction(100, "2", 995_000));
+
+        assert_ne!(base, transaction_digest(&sample_transaction(101, "2", 995_000)));
+        assert_ne!(base, transaction_digest(&sample_transaction(100, "3", 995_000)));
+        assert_ne!(base, transaction_digest(&sample_transaction(100, "2", 990_000)));
+    }
+
+    #[test]
+    fn test_inner_instructions_alter_digest() {
+        let honest = sample_transaction(100, "2", 995_000);
+        let mut lying = sample_transaction(100, "2", 995_000);
+        lying.transaction.meta.as_mut().unwrap().inner_instructions =
+            OptionSerializer::Some(vec![UiInnerInstructions {
+                index: 0,
+                instructions: vec![UiInstruction::Compiled(UiCompiledInstruction {
+                    program_id_index: 1,
+                    accounts: vec![0],
+                    data: "2".to_string(),
+                    stack_height: Some(2),
+                })],
+            }]);
+
+        assert_ne!(transaction_digest(&honest), transaction_digest(&lying));
+    }
+
+    #[test]
+    fn test_lookup_tables_alter_digest() {
+        let with_loaded = |readonly: &str| {
+            let mut tx = sample_transaction(100, "2", 995_000);
+            tx.transaction.meta.as_mut().unwrap().loaded_addresses =
+                OptionSerializer::Some(UiLoadedAddresses {
+                    writable: vec![],
+                    readonly: vec![readonly.to_string()],
+                });
+            tx
+        };
+        let honest = with_loaded("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+        let swapped = with_loaded("11111111111111111111111111111111");
+        assert_ne!(transaction_digest(&honest), transaction_digest(&swapped));
+
+        let mut other_table = with_loaded("wormDTUJ6AWPNvk59vGQbDvGJmqbDTdgWgAqcLBCgUb");
+        let EncodedTransaction::Json(ui_tx) = &mut other_table.transaction.transaction else {
+            unreachable!()
+        };
+        let UiMessage::Raw(msg) = &mut ui_tx.message else {
+            unreachable!()
+        };
+        msg.address_table_lookups = Some(vec![UiAddressTableLookup {
+            account_key: "11111111111111111111111111111111".to_string(),
+            writable_indexes: vec![],
+            readonly_indexes: vec![0],
+        }]);
+        assert_ne!(transaction_digest(&honest), transaction_digest(&other_table));
+    }
+
+    /// The sample transaction re-encoded as binary
+    fn binary_transaction(
+        slot: u64,
+        data: &str,
+        encoding: UiTransactionEncoding,
+    ) -> EncodedConfirmedTransactionWithStatusMeta {
+        let mut tx = sample_transaction(slot, data, 995_000);
+        let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction else {
+            unreachable!()
+        };
+        let UiMessage::Raw(msg) = &ui_tx.message else {
+            unreachable!()
+        };
+
+        let instructions = msg
+            .instructions
+            .iter()
+            .map(|ix| {
+                let data = bs58::decode(&ix.data).into_vec().unwrap();
+                let accounts = ix.accounts.clone();
+                CompiledInstruction::new_from_raw_parts(ix.program_id_index, data, accounts)
+            })
+            .collect();
+        let versioned = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(Message::new_with_compiled_instructions(
+                msg.header.num_required_signatures,
+                msg.header.num_readonly_signed_accounts,
+                msg.header.num_readonly_unsigned_accounts,
+                msg.account_keys.iter().map(|key| key.parse().unwrap()).collect(),
+                Hash::default(),
+                instructions,
+            )),
+        };
+
+        tx.transaction = EncodedTransactionWithStatusMeta {
+            transaction: versioned.encode_with_meta(encoding, &TransactionStatusMeta::default()),
+            ..tx.transaction
+        };
+        tx
+    }
+
+    #[test]
+    fn test_binary_encodings_digest_decoded_message() {
+        use UiTransactionEncoding::{Base58, Base64};
+
+        let json = transaction_digest(&sample_transaction(100, "2", 995_000));
+        let base64 = transaction_digest(&binary_transaction(100, "2", Base64));
+
+        assert_eq!(base64, json);
+        assert_eq!(transaction_digest(&binary_transaction(100, "2", Base58)), json);
+        assert_ne!(base64, transaction_digest(&binary_transaction(100, "3", Base64)));
+    }
+}
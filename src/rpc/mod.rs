@@ -1,5 +1,12 @@
-mod client;
-mod consensus;
-
-pub use client::MultiRpcClient;
-pub use consensus::ConsensusEngine;
+mod client;
+mod consensus;
+mod digest;
+
+pub use client::{MultiRpcClient, SignatureRetention};
+pub use consensus::{ConsensusEngine, ConsensusOutcome};
+pub use digest::{transaction_digest, TransactionDigest};
+
+#[cfg(test)]
+pub(crate) use client::tests::{mock_rpc, mock_server, signature_status};
+#[cfg(test)]
+pub(crate) use digest::tests::sample_transaction;
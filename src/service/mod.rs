@@ -1,220 +1,383 @@
-use std::sync::Arc;
-use tokio::time;
-use tracing::{error, info};
-
-use crate::api::{create_router, ws_handler, ApiState, WsState};
-use crate::config::Config;
-use crate::error::Result;
-use crate::monitor::HealthMonitor;
-use crate::rpc::MultiRpcClient;
-use crate::types::SlotObservation;
-use crate::verification::VerificationEngine;
-
-/// Main verification service
-pub struct VerificationService {
-    config: Arc<Config>,
-    health_monitor: Arc<HealthMonitor>,
-    rpc_client: Arc<MultiRpcClient>,
-    verification_engine: Arc<VerificationEngine>,
-    ws_state: WsState,
-    health_check_counter: Arc<std::sync::atomic::AtomicU64>,
-}
-
-impl VerificationService {
-    pub fn new(config: Config) -> Result<Self> {
-        config.validate()?;
-
-        let health_monitor = Arc::new(HealthMonitor::new(
-            config.monitoring.stale_threshold_seconds,
-            config.monitoring.slot_retention_seconds,
-        ));
-
-        let rpc_client = Arc::new(MultiRpcClient::new(
-            config.rpc.endpoints.clone(),
-            config.rpc.consensus_threshold,
-        ));
-
-        let verification_engine = Arc::new(VerificationEngine::new(
-            Arc::clone(&rpc_client),
-            Arc::clone(&health_monitor),
-        ));
-
-        let ws_state = WsState::new();
-
-        Ok(Self {
-            config: Arc::new(config),
-            health_monitor,
-            rpc_client,
-            verification_engine,
-            ws_state,
-            health_check_counter: Arc::new(std::sync::atomic::AtomicU64::new(0)),
-        })
-    }
-
-    /// Start the verification service with API servers
-    pub async fn run(self: Arc<Self>) -> Result<()> {
-        info!("Starting StauroX Verification Service");
-        info!("Network: {}", self.config.network.name());
-        info!(
-            "Configured with {} RPC endpoints",
-            self.rpc_client.client_count()
-        );
-        info!(
-            "Consensus threshold: {}/{}",
-            self.rpc_client.consensus_threshold(),
-            self.rpc_client.client_count()
-        );
-
-        // Start health monitoring
-        let health_task = {
-            let service = Arc::clone(&self);
-            tokio::spawn(async move {
-                service.start_health_monitoring().await
-            })
-        };
-
-        // Start REST API
-        let rest_task = {
-            let service = Arc::clone(&self);
-            tokio::spawn(async move {
-                service.start_rest_api().await
-            })
-        };
-
-        // Start WebSocket server
-        let ws_task = {
-            let service = Arc::clone(&self);
-            tokio::spawn(async move {
-                service.start_websocket_server().await
-            })
-        };
-
-        // Wait for all tasks
-        let (health_result, rest_result, ws_result) = tokio::try_join!(health_task, rest_task, ws_task)
-            .map_err(|e| crate::error::StauroXError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                format!("Task error: {}", e)
-            )))?;
-
-        // Handle individual task results
-        health_result?;
-        rest_result?;
-        ws_result?;
-
-        Ok(())
-    }
-
-    /// Start REST API server
-    async fn start_rest_api(&self) -> Result<()> {
-        let api_state = ApiState {
-            engine: Arc::clone(&self.verification_engine),
-        };
-
-        let app = create_router(api_state);
-
-        let addr = format!("0.0.0.0:{}", self.config.api.rest_port);
-        info!("REST API listening on {}", addr);
-
-        let listener = tokio::net::TcpListener::bind(&addr)
-            .await
-            .map_err(|e| crate::error::StauroXError::Io(e))?;
-
-        axum::serve(listener, app)
-            .await
-            .map_err(|e| crate::error::StauroXError::Io(
-                std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
-            ))?;
-
-        Ok(())
-    }
-
-    /// Start WebSocket server
-    async fn start_websocket_server(&self) -> Result<()> {
-        use axum::routing::get;
-
-        let ws_state = self.ws_state.clone();
-        let app = axum::Router::new()
-            .route("/events", get(ws_handler))
-            .with_state(ws_state);
-
-        let addr = format!("0.0.0.0:{}", self.config.api.websocket_port);
-        info!("WebSocket server listening on {}", addr);
-
-        let listener = tokio::net::TcpListener::bind(&addr)
-            .await
-            .map_err(|e| crate::error::StauroXError::Io(e))?;
-
-        axum::serve(listener, app)
-            .await
-            .map_err(|e| crate::error::StauroXError::Io(
-                std::io::Error::new(std::io::ErrorKind::Other, e.to_string())
-            ))?;
-
-        Ok(())
-    }
-
-    /// Main health monitoring loop
-    async fn start_health_monitoring(&self) -> Result<()> {
-        let mut interval = time::interval(self.config.health_check_interval());
-
-        info!("Starting network health monitoring...");
-
-        loop {
-            interval.tick().await;
-
-            if let Err(e) = self.health_check_cycle().await {
-                error!("Health check error: {}", e);
-            }
-        }
-    }
-
-    /// Single health check cycle
-    async fn health_check_cycle(&self) -> Result<()> {
-        let slot = self.rpc_client.get_slot_with_consensus().await?;
-
-        self.health_monitor
-            .record_observation(SlotObservation::new(slot, "consensus"))
-            .await;
-
-        let health = self.health_monitor.check_health().await;
-        
-        // Increment counter
-        let count = self.health_check_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
-        
-        // Only log every 100 checks (~40 seconds) or on first check
-        if count == 0 || count % 100 == 0 {
-            info!("Health: {:?} | Slot: {} | Uptime: {}s", health, slot, count);
-        }
-
-        Ok(())
-    }
-
-    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
-        Arc::clone(&self.health_monitor)
-    }
-
-    pub fn rpc_client(&self) -> Arc<MultiRpcClient> {
-        Arc::clone(&self.rpc_client)
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_service_creation() {
-        let config = Config::default();
-        let service = VerificationService::new(config);
-        assert!(service.is_ok());
-    }
-
-    #[test]
-    fn test_service_rejects_invalid_config() {
-        let mut config = Config::default();
-        config.rpc.endpoints.clear();
-        
-        let service = VerificationService::new(config);
-        assert!(service.is_err());
-    }
+use std::sync::Arc;
+use tokio::time;
+use tracing::{debug, error, info};
+
+use crate::api::{create_router, ws_handler, ApiState, VerificationEvent, WsState};
+use crate::config::Config;
+use crate::error::Result;
+use crate::monitor::{HealthMonitor, StakeMonitor};
+use crate::parsers::TransactionParser;
+use crate::rpc::MultiRpcClient;
+use crate::storage::{AsyncStore, SqliteStore, VerificationStore};
+use crate::verification::{
+    AuditLog, ReorgWatch, ResultCache, RiskScorer, VerificationEngine, WatchUpdate,
+};
+
+/// Main verification service
+pub struct VerificationService {
+    config: Arc<Config>,
+    health_monitor: Arc<HealthMonitor>,
+    stake_monitor: Arc<StakeMonitor>,
+    reorg_watch: Arc<ReorgWatch>,
+    result_cache: Arc<ResultCache>,
+    store: Option<AsyncStore>,
+    rpc_client: Arc<MultiRpcClient>,
+    verification_engine: Arc<VerificationEngine>,
+    ws_state: WsState,
+    health_check_counter: Arc<std::sync::atomic::AtomicU64>,
+}
+
+impl VerificationService {
+    pub fn new(config: Config) -> Result<Self> {
+        config.validate()?;
+
+        let health_monitor = Arc::new(HealthMonitor::new(
+            config.monitoring.stale_threshold_seconds,
+            config.monitoring.slot_retention_seconds,
+        ));
+
+        let stake_monitor = Arc::new(StakeMonitor::new());
+        let reorg_watch = Arc::new(ReorgWatch::new());
+        let result_cache = Arc::new(ResultCache::new(config.api.cache_size, config.cache_ttl()));
+
+        let mut rpc_client = MultiRpcClient::new(
+            config.rpc.endpoints.clone(),
+            config.rpc.consensus_threshold,
+            config.request_timeout(),
+        )
+        .with_weights(config.rpc.endpoint_weights.clone());
+
+        if let Some(fraction) = config.rpc.weighted_quorum {
+            rpc_client = rpc_client.with_weighted_quorum(fraction);
+        }
+        let rpc_client = Arc::new(rpc_client);
+
+        let store: Option<Arc<dyn VerificationStore>> = match &config.storage.path {
+            Some(path) => {
+                info!("Persisting verifications to {}", path);
+                Some(Arc::new(SqliteStore::open(path)?))
+            }
+            None => None,
+        };
+
+        let mut verification_engine =
+            VerificationEngine::new(Arc::clone(&rpc_client), Arc::clone(&health_monitor))
+                .with_stake_monitor(Arc::clone(&stake_monitor))
+                .with_reorg_watch(Arc::clone(&reorg_watch))
+                .with_parser(TransactionParser::for_network(config.network))
+                .with_batch_concurrency(config.rpc.batch_concurrency)
+                .with_risk_scorer(RiskScorer::new().with_weights(config.risk.weights.clone()))
+                .with_amount_tiers(config.risk.amount_tiers.clone())
+                .with_audit_log(Arc::new(AuditLog::new(config.api.audit_log_size)))
+                .with_cache(Arc::clone(&result_cache));
+
+        if let Some(store) = &store {
+            verification_engine = verification_engine.with_store(Arc::clone(store));
+        }
+        let verification_engine = Arc::new(verification_engine);
+        let store = store.map(AsyncStore::new);
+
+        let ws_state = WsState::new()
+            .with_engine(Arc::clone(&verification_engine), config.max_finality_wait());
+
+        Ok(Self {
+            config: Arc::new(config),
+            health_monitor,
+            stake_monitor,
+            reorg_watch,
+            result_cache,
+            store,
+            rpc_client,
+            verification_engine,
+            ws_state,
+            health_check_counter: Arc::new(std::sync::atomic::AtomicU64::new(0)),
+        })
+    }
+
+    /// Start the verification service with API servers
+    pub async fn run(self: Arc<Self>) -> Result<()> {
+        info!("Starting StauroX Verification Service");
+        info!("Network: {}", self.config.network.name());
+        info!(
+            "Configured with {} RPC endpoints",
+            self.rpc_client.client_count()
+        );
+        info!(
+            "Consensus threshold: {}/{}",
+            self.rpc_client.consensus_threshold(),
+            self.rpc_client.client_count()
+        );
+        if let Some(required_weight) = self.rpc_client.required_weight() {
+            info!("Weighted quorum: {:.2} endpoint weight required", required_weight);
+        }
+
+        // Start health monitoring
+        let health_task = {
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.start_health_monitoring().await
+            })
+        };
+
+        // Start vote account tracking
+        let stake_task = {
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.start_stake_monitoring().await
+            })
+        };
+
+        // Start reorg watch over non-finalized verifications
+        let reorg_task = {
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.start_reorg_watch().await
+            })
+        };
+
+        // Start REST API
+        let rest_task = {
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.start_rest_api().await
+            })
+        };
+
+        // Start WebSocket server
+        let ws_task = {
+            let service = Arc::clone(&self);
+            tokio::spawn(async move {
+                service.start_websocket_server().await
+            })
+        };
+
+        // Wait for all tasks
+        let (health_result, stake_result, reorg_result, rest_result, ws_result) =
+            tokio::try_join!(health_task, stake_task, reorg_task, rest_task, ws_task)
+            .map_err(|e| crate::error::StauroXError::Io(std::io::Error::other(
+                format!("Task error: {}", e)
+            )))?;
+
+        // Handle individual task results
+        health_result?;
+        stake_result?;
+        reorg_result?;
+        rest_result?;
+        ws_result?;
+
+        Ok(())
+    }
+
+    /// Start REST API server
+    async fn start_rest_api(&self) -> Result<()> {
+        let api_state = ApiState {
+            engine: Arc::clone(&self.verification_engine),
+            max_finality_wait: self.config.max_finality_wait(),
+            max_batch_size: self.config.api.max_batch_size,
+            policies: Arc::new(self.config.policies.clone()),
+        };
+
+        let app = create_router(api_state);
+
+        let addr = format!("0.0.0.0:{}", self.config.api.rest_port);
+        info!("REST API listening on {}", addr);
+
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .map_err(crate::error::StauroXError::Io)?;
+
+        axum::serve(listener, app)
+            .await
+            .map_err(|e| crate::error::StauroXError::Io(
+                std::io::Error::other(e.to_string())
+            ))?;
+
+        Ok(())
+    }
+
+    /// Start WebSocket server
+    async fn start_websocket_server(&self) -> Result<()> {
+        use axum::routing::get;
+
+        let ws_state = self.ws_state.clone();
+        let app = axum::Router::new()
+            .route("/events", get(ws_handler))
+            .with_state(ws_state);
+
+        let addr = format!("0.0.0.0:{}", self.config.api.websocket_port);
+        info!("WebSocket server listening on {}", addr);
+
+        let listener = tokio::net::TcpListener::bind(&addr)
+            .await
+            .map_err(crate::error::StauroXError::Io)?;
+
+        axum::serve(listener, app)
+            .await
+            .map_err(|e| crate::error::StauroXError::Io(
+                std::io::Error::other(e.to_string())
+            ))?;
+
+        Ok(())
+    }
+
+    /// Main health monitoring loop
+    async fn start_health_monitoring(&self) -> Result<()> {
+        let mut interval = time::interval(self.config.health_check_interval());
+
+        info!("Starting network health monitoring...");
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = self.health_check_cycle().await {
+                error!("Health check error: {}", e);
+            }
+        }
+    }
+
+    /// Vote account refresh loop feeding stake confirmation
+    async fn start_stake_monitoring(&self) -> Result<()> {
+        let mut interval = time::interval(self.config.vote_refresh_interval());
+
+        info!("Starting vote account tracking...");
+
+        loop {
+            interval.tick().await;
+
+            match self.rpc_client.get_vote_accounts().await {
+                Ok(status) => self.stake_monitor.update(&status).await,
+                Err(e) => error!("Vote account refresh error: {}", e),
+            }
+        }
+    }
+
+    /// Re-check non-finalized verifications, re-verifying them as they
+    /// finalize and persisting and broadcasting revocations
+    async fn start_reorg_watch(&self) -> Result<()> {
+        let mut interval = time::interval(self.config.reorg_check_interval());
+
+        info!("Starting reorg watch...");
+
+        loop {
+            interval.tick().await;
+
+            for update in self.reorg_watch.recheck(&self.rpc_client).await {
+                match update {
+                    WatchUpdate::Finalized(signature) => {
+                        if let Err(e) = self.verification_engine.reverify(&signature).await {
+                            error!("Failed to re-verify finalized {}: {}", signature, e);
+                        }
+                    }
+                    WatchUpdate::Moved { signature, slot } => {
+                        if let Err(e) = self.verification_engine.reverify(&signature).await {
+                            error!("Failed to re-verify {} at slot {}: {}", signature, slot, e);
+                        }
+                    }
+                    WatchUpdate::Revoked(result) => {
+                        if let Some(store) = &self.store {
+                            if let Err(e) = store.save((*result).clone()).await {
+                                error!("Failed to persist revocation of {}: {}", result.signature, e);
+                            }
+                        }
+                        self.result_cache.invalidate(&result.signature).await;
+                        self.ws_state.broadcast(VerificationEvent::from(*result));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Single health check cycle
+    ///
+    /// Polls every endpoint on its own and records one observation per URL,
+    /// including the block it holds at the slot all endpoints were sampled at.
+    /// A failed poll records nothing, so that source's last observation ages
+    /// into staleness instead of masking the failure.
+    async fn health_check_cycle(&self) -> Result<()> {
+        let polls = self.rpc_client.observe_slot_per_endpoint().await;
+
+        let mut highest_slot = None;
+        let mut failed = 0;
+        for (source, result) in polls {
+            match result {
+                Ok(mut observation) => {
+                    highest_slot = highest_slot.max(Some(observation.slot));
+                    if let Some(stake) = self.stake_monitor.stake_percent_at(observation.slot).await {
+                        observation = observation.with_stake(stake);
+                    }
+                    self.health_monitor.record_observation(observation).await;
+                }
+                Err(e) => {
+                    debug!("Slot poll failed for {}: {}", source, e);
+                    failed += 1;
+                }
+            }
+        }
+
+        let health = self.health_monitor.check_health().await;
+        
+        // Increment counter
+        let count = self.health_check_counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        
+        // Only log every 100 checks (~40 seconds) or on first check
+        if count == 0 || count.is_multiple_of(100) {
+            info!(
+                "Health: {:?} | Slot: {:?} | Failed polls: {}/{} | Uptime: {}s",
+                health,
+                highest_slot,
+                failed,
+                self.rpc_client.client_count(),
+                count
+            );
+        }
+
+        if highest_slot.is_none() {
+            return Err(crate::error::StauroXError::health_check(
+                "No RPC endpoint returned a slot",
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn health_monitor(&self) -> Arc<HealthMonitor> {
+        Arc::clone(&self.health_monitor)
+    }
+
+    pub fn stake_monitor(&self) -> Arc<StakeMonitor> {
+        Arc::clone(&self.stake_monitor)
+    }
+
+    pub fn reorg_watch(&self) -> Arc<ReorgWatch> {
+        Arc::clone(&self.reorg_watch)
+    }
+
+    pub fn result_cache(&self) -> Arc<ResultCache> {
+        Arc::clone(&self.result_cache)
+    }
+
+    pub fn rpc_client(&self) -> Arc<MultiRpcClient> {
+        Arc::clone(&self.rpc_client)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_creation() {
+        let config = Config::default();
+        let service = VerificationService::new(config);
+        assert!(service.is_ok());
+    }
+
+    #[test]
+    fn test_service_rejects_invalid_config() {
+        let mut config = Config::default();
+        config.rpc.endpoints.clear();
+        
+        let service = VerificationService::new(config);
+        assert!(service.is_err());
+    }
 }
\ No newline at end of file
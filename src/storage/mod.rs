@@ -0,0 +1,119 @@
+mod sqlite;
+
+pub use sqlite::SqliteStore;
+
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+use std::sync::Arc;
+
+use crate::error::{Result, StauroXError};
+use crate::parsers::{BridgeType, ParsedTransaction};
+use crate::types::VerificationResult;
+
+/// Which way a bridge transaction moves value
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    Outbound,
+    Inbound,
+    TokenOperation,
+}
+
+impl Direction {
+    pub fn of(parsed: &ParsedTransaction) -> Option<Self> {
+        if parsed.is_outbound() {
+            Some(Direction::Outbound)
+        } else if parsed.is_inbound() {
+            Some(Direction::Inbound)
+        } else if parsed.is_token_operation() {
+            Some(Direction::TokenOperation)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Direction::Outbound => "outbound",
+            Direction::Inbound => "inbound",
+            Direction::TokenOperation => "token_operation",
+        }
+    }
+}
+
+/// Filters for stored verifications; unset filters match everything
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StoreQuery {
+    pub min_slot: Option<u64>,
+    pub max_slot: Option<u64>,
+    pub bridge: Option<BridgeType>,
+    pub direction: Option<Direction>,
+    pub target_chain: Option<u16>,
+    /// Most results returned, newest slot first
+    pub limit: Option<usize>,
+}
+
+/// Durable record of verification results, with their parsed transactions
+///
+/// Saving a result replaces any earlier one for the same signature.
+pub trait VerificationStore: Send + Sync {
+    fn save(&self, result: &VerificationResult) -> Result<()>;
+
+    fn get(&self, signature: &Signature) -> Result<Option<VerificationResult>>;
+
+    fn query(&self, query: &StoreQuery) -> Result<Vec<VerificationResult>>;
+}
+
+/// Async handle to a store
+///
+/// Store calls block on disk I/O, so each one runs on tokio's blocking pool
+/// instead of holding up an async worker thread.
+#[derive(Clone)]
+pub struct AsyncStore {
+    inner: Arc<dyn VerificationStore>,
+}
+
+impl AsyncStore {
+    pub fn new(inner: Arc<dyn VerificationStore>) -> Self {
+        Self { inner }
+    }
+
+    pub async fn save(&self, result: VerificationResult) -> Result<()> {
+        self.run(move |store| store.save(&result)).await
+    }
+
+    pub async fn get(&self, signature: Signature) -> Result<Option<VerificationResult>> {
+        self.run(move |store| store.get(&signature)).await
+    }
+
+    pub async fn query(&self, query: StoreQuery) -> Result<Vec<VerificationResult>> {
+        self.run(move |store| store.query(&query)).await
+    }
+
+    async fn run<T, F>(&self, call: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn VerificationStore) -> Result<T> + Send + 'static,
+    {
+        let inner = Arc::clone(&self.inner);
+        tokio::task::spawn_blocking(move || call(inner.as_ref()))
+            .await
+            .map_err(|e| StauroXError::verification(format!("Store task failed: {}", e)))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_async_store_round_trip() {
+        let store = AsyncStore::new(Arc::new(SqliteStore::in_memory().unwrap()));
+        let result = VerificationResult::new(Signature::new_unique(), 100).with_verification(true);
+        store.save(result.clone()).await.unwrap();
+
+        let stored = store.get(result.signature).await.unwrap().unwrap();
+        assert!(stored.verified);
+        assert_eq!(store.query(StoreQuery::default()).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,275 @@
+use rusqlite::types::Value;
+use rusqlite::{params, params_from_iter, Connection, OptionalExtension};
+use solana_sdk::signature::Signature;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::error::{Result, StauroXError};
+use crate::types::VerificationResult;
+
+use super::{Direction, StoreQuery, VerificationStore};
+
+/// Rows returned by a query that sets no limit
+const DEFAULT_QUERY_LIMIT: usize = 100;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS verifications (
+        signature    TEXT PRIMARY KEY,
+        slot         INTEGER NOT NULL,
+        verified     INTEGER NOT NULL,
+        bridge       TEXT,
+        direction    TEXT,
+        result       TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS verifications_slot ON verifications (slot);
+    CREATE INDEX IF NOT EXISTS verifications_bridge ON verifications (bridge, direction);
+    CREATE TABLE IF NOT EXISTS verification_chains (
+        signature    TEXT NOT NULL,
+        target_chain INTEGER NOT NULL,
+        PRIMARY KEY (signature, target_chain)
+    );
+    CREATE INDEX IF NOT EXISTS verification_chains_chain ON verification_chains (target_chain);
+";
+
+/// SQLite-backed store
+///
+/// Each result is kept as JSON alongside the columns queries filter on.
+/// A transaction can send to several chains, so target chains get a row each
+/// in `verification_chains`.
+pub struct SqliteStore {
+    conn: Mutex<Connection>,
+}
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::init(Connection::open(path)?)
+    }
+
+    pub fn in_memory() -> Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> Result<std::sync::MutexGuard<'_, Connection>> {
+        self.conn
+            .lock()
+            .map_err(|_| StauroXError::verification("Verification store lock poisoned"))
+    }
+}
+
+impl VerificationStore for SqliteStore {
+    fn save(&self, result: &VerificationResult) -> Result<()> {
+        let parsed = result.parsed_transaction.as_ref();
+        let bridge = parsed.map(|parsed| parsed.bridge_type.as_str());
+        let direction = parsed.and_then(Direction::of).map(|direction| direction.as_str());
+        let target_chains = parsed.map(|parsed| parsed.target_chains()).unwrap_or_default();
+        let signature = result.signature.to_string();
+
+        let mut conn = self.conn()?;
+        let tx = conn.transaction()?;
+        tx.execute(
+            "INSERT OR REPLACE INTO verifications
+                (signature, slot, verified, bridge, direction, result)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                signature,
+                result.slot as i64,
+                result.verified,
+                bridge,
+                direction,
+                serde_json::to_string(result)?,
+            ],
+        )?;
+        tx.execute("DELETE FROM verification_chains WHERE signature = ?1", params![signature])?;
+        for target_chain in target_chains {
+            tx.execute(
+                "INSERT INTO verification_chains (signature, target_chain) VALUES (?1, ?2)",
+                params![signature, target_chain],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn get(&self, signature: &Signature) -> Result<Option<VerificationResult>> {
+        let json: Option<String> = self
+            .conn()?
+            .query_row(
+                "SELECT result FROM verifications WHERE signature = ?1",
+                params![signature.to_string()],
+                |row| row.get(0),
+            )
+            .optional()?;
+
+        Ok(json.map(|json| serde_json::from_str(&json)).transpose()?)
+    }
+
+    fn query(&self, query: &StoreQuery) -> Result<Vec<VerificationResult>> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+
+        if let Some(min_slot) = query.min_slot {
+            conditions.push("slot >= ?");
+            values.push(Value::Integer(min_slot as i64));
+        }
+        if let Some(max_slot) = query.max_slot {
+            conditions.push("slot <= ?");
+            values.push(Value::Integer(max_slot as i64));
+        }
+        if let Some(bridge) = &query.bridge {
+            conditions.push("bridge = ?");
+            values.push(Value::Text(bridge.as_str().to_string()));
+        }
+        if let Some(direction) = query.direction {
+            conditions.push("direction = ?");
+            values.push(Value::Text(direction.as_str().to_string()));
+        }
+        if let Some(target_chain) = query.target_chain {
+            conditions.push(
+                "EXISTS (SELECT 1 FROM verification_chains chains
+                         WHERE chains.signature = verifications.signature
+                           AND chains.target_chain = ?)",
+            );
+            values.push(Value::Integer(target_chain.into()));
+        }
+
+        let mut sql = String::from("SELECT result FROM verifications");
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY slot DESC LIMIT ?");
+        values.push(Value::Integer(query.limit.unwrap_or(DEFAULT_QUERY_LIMIT) as i64));
+
+        let conn = self.conn()?;
+        let mut statement = conn.prepare(&sql)?;
+        let rows = statement.query_map(params_from_iter(values), |row| row.get::<_, String>(0))?;
+
+        let mut results = Vec::new();
+        for json in rows {
+            results.push(serde_json::from_str(&json?)?);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{
+        BridgeCall, BridgeInstruction, BridgeType, InstructionLocation, ParsedTransaction,
+    };
+    use crate::types::FinalityLevel;
+
+    fn transfer(slot: u64, target_chain: u16) -> VerificationResult {
+        VerificationResult::new(Signature::new_unique(), slot)
+            .with_verification(true)
+            .with_finality(FinalityLevel::UltraSafe)
+            .with_parsed_transaction(Some(ParsedTransaction::new(
+                BridgeType::Wormhole,
+                BridgeInstruction::TransferNative {
+                    amount: 1_000,
+                    target_chain,
+                    recipient: vec![0xab; 32],
+                },
+            )))
+    }
+
+    #[test]
+    fn test_save_and_get_round_trip() {
+        let store = SqliteStore::in_memory().unwrap();
+        let result = transfer(100, 2);
+        store.save(&result).unwrap();
+
+        let stored = store.get(&result.signature).unwrap().unwrap();
+        assert_eq!(stored.signature, result.signature);
+        assert_eq!(stored.finality_level, FinalityLevel::UltraSafe);
+        assert_eq!(stored.parsed_transaction.unwrap().recipient(), Some(&[0xab; 32][..]));
+
+        assert!(store.get(&Signature::new_unique()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_query_filters() {
+        let store = SqliteStore::in_memory().unwrap();
+        for (slot, chain) in [(100, 2), (200, 2), (300, 4)] {
+            store.save(&transfer(slot, chain)).unwrap();
+        }
+        store.save(&VerificationResult::new(Signature::new_unique(), 250)).unwrap();
+
+        let ethereum = store
+            .query(&StoreQuery {
+                target_chain: Some(2),
+                direction: Some(Direction::Outbound),
+                ..StoreQuery::default()
+            })
+            .unwrap();
+        let slots: Vec<u64> = ethereum.iter().map(|result| result.slot).collect();
+        assert_eq!(slots, [200, 100]);
+
+        let range = store
+            .query(&StoreQuery {
+                min_slot: Some(150),
+                max_slot: Some(300),
+                bridge: Some(BridgeType::Wormhole),
+                ..StoreQuery::default()
+            })
+            .unwrap();
+        assert_eq!(range.len(), 2);
+
+        let limited = store
+            .query(&StoreQuery {
+                limit: Some(1),
+                ..StoreQuery::default()
+            })
+            .unwrap();
+        assert_eq!(limited[0].slot, 300);
+    }
+
+    #[test]
+    fn test_query_matches_any_target_chain() {
+        let store = SqliteStore::in_memory().unwrap();
+        let transfer = |amount, target_chain| BridgeInstruction::TransferNative {
+            amount,
+            target_chain,
+            recipient: vec![0xab; 32],
+        };
+        let top_level = |outer_index| InstructionLocation { outer_index, inner_index: None };
+        let mixed = VerificationResult::new(Signature::new_unique(), 100).with_parsed_transaction(
+            Some(ParsedTransaction::from_calls(
+                BridgeType::Wormhole,
+                vec![
+                    BridgeCall::new(top_level(0), transfer(1_000, 2)),
+                    BridgeCall::new(top_level(1), transfer(2_000, 4)),
+                ],
+            )),
+        );
+        store.save(&mixed).unwrap();
+        // Saving again replaces the chain rows rather than duplicating them
+        store.save(&mixed).unwrap();
+
+        for chain in [2, 4] {
+            let matches = store
+                .query(&StoreQuery {
+                    target_chain: Some(chain),
+                    ..StoreQuery::default()
+                })
+                .unwrap();
+            assert_eq!(matches.len(), 1);
+        }
+        let none = store
+            .query(&StoreQuery {
+                target_chain: Some(5),
+                ..StoreQuery::default()
+            })
+            .unwrap();
+        assert!(none.is_empty());
+    }
+}
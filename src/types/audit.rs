@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::transaction::TransactionError;
+
+use super::network::{ForkReport, NetworkHealth, SourceLag};
+use super::verification::{Commitment, FinalityLevel};
+
+/// How an endpoint answered a consensus query
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointVerdict {
+    Agreed,
+    Dissented,
+    Failed,
+}
+
+/// One endpoint's part in a consensus query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndpointAnswer {
+    pub endpoint: String,
+    pub verdict: EndpointVerdict,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latency_ms: Option<u64>,
+}
+
+/// Share of the risk score added by one component
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RiskContribution {
+    pub component: String,
+    pub weight: f64,
+    /// Weight times the component's raw risk
+    pub contribution: f64,
+}
+
+/// What one verification pipeline step saw and decided
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum AuditStep {
+    NetworkHealth {
+        health: NetworkHealth,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        fork: Option<ForkReport>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        lagging: Vec<SourceLag>,
+    },
+    TransactionFetch {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        slot: Option<u64>,
+        endpoints: Vec<EndpointAnswer>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// No bridge and no error means the transaction isn't a bridge transaction
+    Parse {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bridge: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    ExecutionStatus {
+        succeeded: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<TransactionError>,
+    },
+    Finality {
+        /// Highest slot observed by the health monitor at the time
+        #[serde(skip_serializing_if = "Option::is_none")]
+        current_slot: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        commitment: Option<Commitment>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        confirmations: Option<usize>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        stake_percent: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        finality: Option<FinalityLevel>,
+        endpoints: Vec<EndpointAnswer>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    Risk {
+        components: Vec<RiskContribution>,
+        score: f64,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub step: AuditStep,
+}
+
+/// Per-step record of how a verification result was reached
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AuditTrail {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl AuditTrail {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, step: AuditStep) {
+        self.entries.push(AuditEntry {
+            recorded_at: Utc::now(),
+            step,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_audit_trail_round_trip() {
+        let mut trail = AuditTrail::new();
+        trail.record(AuditStep::NetworkHealth {
+            health: NetworkHealth::Healthy,
+            fork: None,
+            lagging: vec![],
+        });
+        trail.record(AuditStep::ExecutionStatus {
+            succeeded: false,
+            error: Some(TransactionError::InsufficientFundsForFee),
+        });
+
+        let json = serde_json::to_value(&trail).unwrap();
+        assert_eq!(json["entries"][0]["step"], "network_health");
+        assert_eq!(json["entries"][1]["step"], "execution_status");
+
+        let decoded: AuditTrail = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, trail);
+    }
+}
@@ -1,6 +1,13 @@
-pub mod network;
-pub mod verification;
-
-// Re-export commonly used types
-pub use network::{NetworkHealth, SlotObservation};
-pub use verification::{FinalityLevel, VerificationResult};
+pub mod audit;
+pub mod network;
+pub mod policy;
+pub mod verification;
+
+// Re-export commonly used types
+pub use audit::{AuditEntry, AuditStep, AuditTrail, EndpointAnswer, EndpointVerdict, RiskContribution};
+pub use network::{ForkBranch, ForkReport, NetworkHealth, SlotObservation, SourceLag};
+pub use policy::{AcceptancePolicy, AmountFinality, PolicyReport, RuleCheck};
+pub use verification::{
+    Commitment, FailureReason, FinalityLevel, Revocation, SignatureConfirmation,
+    VerificationOutcome, VerificationResult,
+};
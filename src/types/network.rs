@@ -1,72 +1,154 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-
-/// Network health status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-pub enum NetworkHealth {
-    Healthy,
-    Forked,
-    Halted,
-}
-
-impl NetworkHealth {
-    pub fn is_operational(&self) -> bool {
-        matches!(self, NetworkHealth::Healthy)
-    }
-
-    pub fn is_degraded(&self) -> bool {
-        !self.is_operational()
-    }
-}
-
-/// Slot observation from a single RPC source
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SlotObservation {
-    pub slot: u64,
-    pub source: String,
-    pub timestamp: DateTime<Utc>,
-    pub stake_percent: Option<f64>,
-}
-
-impl SlotObservation {
-    pub fn new(slot: u64, source: impl Into<String>) -> Self {
-        Self {
-            slot,
-            source: source.into(),
-            timestamp: Utc::now(),
-            stake_percent: None,
-        }
-    }
-
-    pub fn with_stake(mut self, stake_percent: f64) -> Self {
-        self.stake_percent = Some(stake_percent);
-        self
-    }
-
-    pub fn age_seconds(&self) -> i64 {
-        (Utc::now() - self.timestamp).num_seconds()
-    }
-
-    pub fn is_stale(&self, threshold_secs: i64) -> bool {
-        self.age_seconds() > threshold_secs
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_network_health_states() {
-        assert!(NetworkHealth::Healthy.is_operational());
-        assert!(!NetworkHealth::Forked.is_operational());
-        assert!(NetworkHealth::Halted.is_degraded());
-    }
-
-    #[test]
-    fn test_slot_observation_builder() {
-        let obs = SlotObservation::new(12345, "rpc1").with_stake(25.5);
-        assert_eq!(obs.slot, 12345);
-        assert_eq!(obs.stake_percent, Some(25.5));
-    }
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Network health status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NetworkHealth {
+    Healthy,
+    /// A minority of sources lag well behind the rest
+    Degraded,
+    /// Slots still advance, but far below the expected production rate
+    Slow,
+    Forked,
+    Halted,
+}
+
+impl NetworkHealth {
+    /// Lagging sources are outvoted by consensus, so Degraded still verifies
+    pub fn is_operational(&self) -> bool {
+        matches!(self, NetworkHealth::Healthy | NetworkHealth::Degraded)
+    }
+
+    /// Ordering used when several checks disagree; the worst one wins
+    pub fn severity(&self) -> u8 {
+        match self {
+            NetworkHealth::Healthy => 0,
+            NetworkHealth::Degraded => 1,
+            NetworkHealth::Slow => 2,
+            NetworkHealth::Forked => 3,
+            NetworkHealth::Halted => 4,
+        }
+    }
+
+    pub fn worst(self, other: NetworkHealth) -> NetworkHealth {
+        if other.severity() > self.severity() {
+            other
+        } else {
+            self
+        }
+    }
+
+    pub fn is_degraded(&self) -> bool {
+        !matches!(self, NetworkHealth::Healthy)
+    }
+}
+
+/// Slot observation from a single RPC source
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlotObservation {
+    pub slot: u64,
+    pub source: String,
+    pub timestamp: DateTime<Utc>,
+    pub stake_percent: Option<f64>,
+    /// Slot of the block identity below; sources are sampled at a common
+    /// slot, which can trail their own `slot`
+    #[serde(default)]
+    pub block_slot: Option<u64>,
+    /// Blockhash this source holds at `block_slot`, if the block could be fetched
+    #[serde(default)]
+    pub blockhash: Option<String>,
+    #[serde(default)]
+    pub parent_slot: Option<u64>,
+}
+
+impl SlotObservation {
+    pub fn new(slot: u64, source: impl Into<String>) -> Self {
+        Self {
+            slot,
+            source: source.into(),
+            timestamp: Utc::now(),
+            stake_percent: None,
+            block_slot: None,
+            blockhash: None,
+            parent_slot: None,
+        }
+    }
+
+    pub fn with_block(mut self, block_slot: u64, blockhash: impl Into<String>, parent_slot: u64) -> Self {
+        self.block_slot = Some(block_slot);
+        self.blockhash = Some(blockhash.into());
+        self.parent_slot = Some(parent_slot);
+        self
+    }
+
+    pub fn with_stake(mut self, stake_percent: f64) -> Self {
+        self.stake_percent = Some(stake_percent);
+        self
+    }
+
+    pub fn age_seconds(&self) -> i64 {
+        (Utc::now() - self.timestamp).num_seconds()
+    }
+
+    pub fn is_stale(&self, threshold_secs: i64) -> bool {
+        self.age_seconds() > threshold_secs
+    }
+}
+
+/// How far one source trails the rest of the network
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceLag {
+    pub source: String,
+    pub slot: u64,
+    /// Slots behind the median of fresh sources
+    pub lag_slots: u64,
+}
+
+/// One side of a fork: sources that hold the same block at the contested slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkBranch {
+    pub blockhash: String,
+    pub parent_slot: Option<u64>,
+    pub sources: Vec<String>,
+}
+
+/// Sources reporting different blocks for the same slot
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkReport {
+    pub slot: u64,
+    pub branches: Vec<ForkBranch>,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_health_states() {
+        assert!(NetworkHealth::Healthy.is_operational());
+        assert!(!NetworkHealth::Forked.is_operational());
+        assert!(NetworkHealth::Halted.is_degraded());
+        assert!(!NetworkHealth::Slow.is_operational());
+        assert!(NetworkHealth::Degraded.is_operational());
+        assert!(NetworkHealth::Degraded.is_degraded());
+    }
+
+    #[test]
+    fn test_network_health_worst() {
+        assert_eq!(NetworkHealth::Healthy.worst(NetworkHealth::Slow), NetworkHealth::Slow);
+        assert_eq!(NetworkHealth::Halted.worst(NetworkHealth::Forked), NetworkHealth::Halted);
+    }
+
+    #[test]
+    fn test_slot_observation_builder() {
+        let obs = SlotObservation::new(12345, "rpc1").with_stake(25.5);
+        assert_eq!(obs.slot, 12345);
+        assert_eq!(obs.stake_percent, Some(25.5));
+
+        let obs = obs.with_block(12340, "hash", 12339);
+        assert_eq!(obs.block_slot, Some(12340));
+        assert_eq!(obs.blockhash.as_deref(), Some("hash"));
+        assert_eq!(obs.parent_slot, Some(12339));
+    }
 }
\ No newline at end of file
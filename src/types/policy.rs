@@ -0,0 +1,303 @@
+use serde::{Deserialize, Serialize};
+
+use super::network::NetworkHealth;
+use super::verification::{Commitment, FinalityLevel, VerificationResult};
+use crate::parsers::BridgeType;
+
+/// Rules a verified result must meet to be accepted
+///
+/// Unset rules are not checked. A result must always be verified.
+///
+/// Rules on transfer details fail closed when the transaction doesn't carry
+/// the detail (non-bridge transactions, undecoded instructions):
+/// `bridges`, `target_chains` and `max_amount` fail, and `amount_finality`
+/// requires its strictest entry.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AcceptancePolicy {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_risk: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_finality: Option<FinalityLevel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_commitment: Option<Commitment>,
+    /// Worst network health still accepted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_health: Option<NetworkHealth>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_consensus: Option<u8>,
+    /// Accepted bridges; non-bridge transactions fail this rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bridges: Option<Vec<BridgeType>>,
+    /// Largest total transfer amount accepted, in the token's raw units
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_amount: Option<u64>,
+    /// Finality required from a transfer amount upwards; the entry with the
+    /// highest `min_amount` not above the amount applies
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub amount_finality: Vec<AmountFinality>,
+    /// Accepted Wormhole target chain IDs; every outbound transfer must target one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_chains: Option<Vec<u16>>,
+    /// Require the finality the transfer's amount tier calls for
+    #[serde(default)]
+    pub amount_tier_finality: bool,
+}
+
+/// Finality a policy requires for transfers of at least `min_amount` raw units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AmountFinality {
+    pub min_amount: u64,
+    pub min_finality: FinalityLevel,
+}
+
+/// Outcome of one policy rule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleCheck {
+    pub rule: String,
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// How a result fared against a named policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyReport {
+    pub policy: String,
+    pub accepted: bool,
+    pub rules: Vec<RuleCheck>,
+}
+
+impl AcceptancePolicy {
+    /// Low risk, at least Safe finality (or what the amount calls for) on an operational network
+    pub fn standard() -> Self {
+        Self {
+            max_risk: Some(0.2),
+            min_finality: Some(FinalityLevel::Safe),
+            max_health: Some(NetworkHealth::Degraded),
+            amount_tier_finality: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn evaluate(&self, name: &str, result: &VerificationResult) -> PolicyReport {
+        let mut rules = vec![check(
+            "verified",
+            result.verified,
+            format!("verified = {}", result.verified),
+        )];
+
+        if let Some(max_risk) = self.max_risk {
+            rules.push(check(
+                "max_risk",
+                result.risk_score < max_risk,
+                format!("risk {:.3} < {:.3}", result.risk_score, max_risk),
+            ));
+        }
+
+        if let Some(min_finality) = self.min_finality {
+            rules.push(check(
+                "min_finality",
+                result.finality_level >= min_finality,
+                format!("{:?} >= {:?}", result.finality_level, min_finality),
+            ));
+        }
+
+        if let Some(min_commitment) = self.min_commitment {
+            rules.push(check(
+                "min_commitment",
+                result.commitment.is_some_and(|commitment| commitment >= min_commitment),
+                format!("{:?} >= {:?}", result.commitment, min_commitment),
+            ));
+        }
+
+        if let Some(max_health) = self.max_health {
+            rules.push(check(
+                "max_health",
+                result.network_health.severity() <= max_health.severity(),
+                format!("{:?} no worse than {:?}", result.network_health, max_health),
+            ));
+        }
+
+        if let Some(min_consensus) = self.min_consensus {
+            rules.push(check(
+                "min_consensus",
+                result.consensus_count >= min_consensus,
+                format!("{} >= {} endpoints", result.consensus_count, min_consensus),
+            ));
+        }
+
+        let parsed = result.parsed_transaction.as_ref();
+
+        if let Some(bridges) = &self.bridges {
+            let bridge = parsed.map(|parsed| &parsed.bridge_type);
+            rules.push(check(
+                "bridges",
+                bridge.is_some_and(|bridge| bridges.contains(bridge)),
+                format!("{:?} in {:?}", bridge, bridges),
+            ));
+        }
+
+        let amount = parsed.and_then(|parsed| parsed.amount());
+
+        if let Some(max_amount) = self.max_amount {
+            rules.push(check(
+                "max_amount",
+                amount.is_some_and(|amount| amount <= max_amount),
+                format!("{:?} <= {}", amount, max_amount),
+            ));
+        }
+
+        if let Some(required) = self.amount_finality(amount) {
+            rules.push(check(
+                "amount_finality",
+                result.finality_level >= required.min_finality,
+                format!(
+                    "{:?} >= {:?} for amount {:?} (tier from {})",
+                    result.finality_level, required.min_finality, amount, required.min_amount
+                ),
+            ));
+        }
+
+        if let Some(target_chains) = &self.target_chains {
+            let chains = parsed.map(|parsed| parsed.target_chains()).unwrap_or_default();
+            rules.push(check(
+                "target_chains",
+                !chains.is_empty() && chains.iter().all(|chain| target_chains.contains(chain)),
+                format!("{:?} in {:?}", chains, target_chains),
+            ));
+        }
+
+        if self.amount_tier_finality {
+            if let Some(required) = result.required_finality {
+                rules.push(check(
+                    "amount_tier_finality",
+                    result.finality_level >= required,
+                    format!("{:?} >= {:?}", result.finality_level, required),
+                ));
+            }
+        }
+
+        PolicyReport {
+            policy: name.to_string(),
+            accepted: rules.iter().all(|rule| rule.passed),
+            rules,
+        }
+    }
+
+    /// Entry of `amount_finality` that applies to `amount`; the strictest one when unknown
+    fn amount_finality(&self, amount: Option<u64>) -> Option<&AmountFinality> {
+        let applicable = self
+            .amount_finality
+            .iter()
+            .filter(|entry| amount.is_none_or(|amount| entry.min_amount <= amount));
+
+        match amount {
+            Some(_) => applicable.max_by_key(|entry| entry.min_amount),
+            None => applicable.max_by_key(|entry| entry.min_finality),
+        }
+    }
+}
+
+fn check(rule: &str, passed: bool, detail: String) -> RuleCheck {
+    RuleCheck {
+        rule: rule.to_string(),
+        passed,
+        detail,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::{BridgeInstruction, ParsedTransaction};
+    use solana_sdk::signature::Signature;
+
+    #[test]
+    fn test_policy_reports_each_rule() {
+        let result = VerificationResult::new(Signature::default(), 100)
+            .with_verification(true)
+            .with_finality(FinalityLevel::Safe)
+            .with_commitment(Commitment::Confirmed)
+            .with_risk_score(0.05)
+            .with_parsed_transaction(Some(ParsedTransaction::new(
+                BridgeType::Wormhole,
+                BridgeInstruction::TransferNative {
+                    amount: 2_000_000,
+                    target_chain: 2,
+                    recipient: vec![],
+                },
+            )));
+
+        let report = AcceptancePolicy::standard().evaluate("default", &result);
+        assert!(report.accepted);
+
+        let finalized_only = AcceptancePolicy {
+            min_commitment: Some(Commitment::Finalized),
+            max_amount: Some(1_000_000),
+            target_chains: Some(vec![2]),
+            ..AcceptancePolicy::default()
+        };
+        let report = finalized_only.evaluate("finalized", &result);
+        assert!(!report.accepted);
+
+        let failed: Vec<&str> = report
+            .rules
+            .iter()
+            .filter(|rule| !rule.passed)
+            .map(|rule| rule.rule.as_str())
+            .collect();
+        assert_eq!(failed, ["min_commitment", "max_amount"]);
+    }
+
+    fn transfer(amount: Option<u64>, finality: FinalityLevel) -> VerificationResult {
+        let instruction = match amount {
+            Some(amount) => BridgeInstruction::TransferNative {
+                amount,
+                target_chain: 2,
+                recipient: vec![],
+            },
+            None => BridgeInstruction::AttestToken,
+        };
+
+        VerificationResult::new(Signature::default(), 100)
+            .with_verification(true)
+            .with_finality(finality)
+            .with_parsed_transaction(Some(ParsedTransaction::new(BridgeType::Wormhole, instruction)))
+    }
+
+    #[test]
+    fn test_amount_conditional_finality() {
+        // UltraSafe for anything over a million, Safe from ten thousand
+        let policy = AcceptancePolicy {
+            amount_finality: vec![
+                AmountFinality { min_amount: 10_000, min_finality: FinalityLevel::Safe },
+                AmountFinality { min_amount: 1_000_001, min_finality: FinalityLevel::UltraSafe },
+            ],
+            ..AcceptancePolicy::default()
+        };
+
+        let accepted = |amount, finality| policy.evaluate("tiered", &transfer(amount, finality)).accepted;
+        assert!(accepted(Some(500), FinalityLevel::Fast));
+        assert!(!accepted(Some(50_000), FinalityLevel::Fast));
+        assert!(accepted(Some(50_000), FinalityLevel::Safe));
+        assert!(!accepted(Some(2_000_000), FinalityLevel::Safe));
+        assert!(accepted(Some(2_000_000), FinalityLevel::UltraSafe));
+
+        // Unknown amounts need the strictest tier
+        assert!(!accepted(None, FinalityLevel::Safe));
+        assert!(accepted(None, FinalityLevel::UltraSafe));
+    }
+
+    #[test]
+    fn test_unknown_transfer_details_fail_closed() {
+        let policy = AcceptancePolicy {
+            max_amount: Some(1_000),
+            ..AcceptancePolicy::default()
+        };
+
+        assert!(policy.evaluate("capped", &transfer(Some(500), FinalityLevel::Fast)).accepted);
+        assert!(!policy.evaluate("capped", &transfer(None, FinalityLevel::Fast)).accepted);
+
+        let non_bridge = VerificationResult::new(Signature::default(), 100).with_verification(true);
+        assert!(!policy.evaluate("capped", &non_bridge).accepted);
+    }
+}
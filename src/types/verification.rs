@@ -1,139 +1,379 @@
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
-use solana_sdk::signature::Signature;
-
-use super::network::NetworkHealth;
-use crate::parsers::ParsedTransaction;
-
-/// Finality confidence levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
-pub enum FinalityLevel {
-    Fast,
-    Safe,
-    UltraSafe,
-}
-
-impl FinalityLevel {
-    pub fn required_stake_percent(&self) -> f64 {
-        match self {
-            FinalityLevel::Fast => 66.0,
-            FinalityLevel::Safe => 80.0,
-            FinalityLevel::UltraSafe => 90.0,
-        }
-    }
-
-    pub fn from_stake_percent(stake: f64) -> Self {
-        if stake >= 90.0 {
-            FinalityLevel::UltraSafe
-        } else if stake >= 80.0 {
-            FinalityLevel::Safe
-        } else {
-            FinalityLevel::Fast
-        }
-    }
-}
-
-/// Complete verification result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct VerificationResult {
-    #[serde(serialize_with = "serialize_signature")]
-    pub signature: Signature,
-    pub slot: u64,
-    pub verified: bool,
-    pub risk_score: f64,
-    pub finality_level: FinalityLevel,
-    pub network_health: NetworkHealth,
-    pub consensus_count: u8,
-    pub timestamp: DateTime<Utc>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub parsed_transaction: Option<ParsedTransaction>,
-}
-
-// Custom serializer for Signature (as string instead of byte array)
-fn serialize_signature<S>(sig: &Signature, serializer: S) -> Result<S::Ok, S::Error>
-where
-    S: serde::Serializer,
-{
-    serializer.serialize_str(&sig.to_string())
-}
-
-impl VerificationResult {
-    pub fn new(signature: Signature, slot: u64) -> Self {
-        Self {
-            signature,
-            slot,
-            verified: false,
-            risk_score: 1.0,
-            finality_level: FinalityLevel::Fast,
-            network_health: NetworkHealth::Healthy,
-            consensus_count: 0,
-            timestamp: Utc::now(),
-            parsed_transaction: None,
-        }
-    }
-
-    pub fn with_verification(mut self, verified: bool) -> Self {
-        self.verified = verified;
-        self
-    }
-
-    pub fn with_consensus(mut self, count: u8) -> Self {
-        self.consensus_count = count;
-        self
-    }
-
-    pub fn with_finality(mut self, level: FinalityLevel) -> Self {
-        self.finality_level = level;
-        self
-    }
-
-    pub fn with_network_health(mut self, health: NetworkHealth) -> Self {
-        self.network_health = health;
-        self
-    }
-
-    pub fn with_risk_score(mut self, score: f64) -> Self {
-        self.risk_score = score.clamp(0.0, 1.0);
-        self
-    }
-
-    pub fn with_parsed_transaction(mut self, parsed: Option<ParsedTransaction>) -> Self {
-        self.parsed_transaction = parsed;
-        self
-    }
-
-    pub fn is_safe(&self) -> bool {
-        self.verified
-            && self.network_health.is_operational()
-            && self.risk_score < 0.2
-            && self.finality_level >= FinalityLevel::Safe
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::str::FromStr;
-
-    #[test]
-    fn test_finality_levels() {
-        assert_eq!(FinalityLevel::Fast.required_stake_percent(), 66.0);
-        assert_eq!(FinalityLevel::from_stake_percent(95.0), FinalityLevel::UltraSafe);
-        assert!(FinalityLevel::UltraSafe > FinalityLevel::Fast);
-    }
-
-    #[test]
-    fn test_verification_result_builder() {
-        let sig = Signature::from_str(
-            "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
-        ).unwrap();
-        
-        let result = VerificationResult::new(sig, 12345)
-            .with_verification(true)
-            .with_consensus(4)
-            .with_finality(FinalityLevel::UltraSafe)
-            .with_risk_score(0.05);
-
-        assert!(result.is_safe());
-    }
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use solana_sdk::signature::Signature;
+use solana_sdk::transaction::TransactionError;
+use solana_transaction_status::TransactionConfirmationStatus;
+
+use super::audit::{AuditTrail, RiskContribution};
+use super::network::NetworkHealth;
+use super::policy::{AcceptancePolicy, PolicyReport};
+use crate::parsers::ParsedTransaction;
+
+/// Finality confidence levels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum FinalityLevel {
+    Fast,
+    Safe,
+    UltraSafe,
+}
+
+impl FinalityLevel {
+    pub fn required_stake_percent(&self) -> f64 {
+        match self {
+            FinalityLevel::Fast => 66.0,
+            FinalityLevel::Safe => 80.0,
+            FinalityLevel::UltraSafe => 90.0,
+        }
+    }
+
+    pub fn from_stake_percent(stake: f64) -> Self {
+        if stake >= 90.0 {
+            FinalityLevel::UltraSafe
+        } else if stake >= 80.0 {
+            FinalityLevel::Safe
+        } else {
+            FinalityLevel::Fast
+        }
+    }
+}
+
+/// Commitment level a transaction has reached on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    Finalized,
+}
+
+impl From<TransactionConfirmationStatus> for Commitment {
+    fn from(status: TransactionConfirmationStatus) -> Self {
+        match status {
+            TransactionConfirmationStatus::Processed => Commitment::Processed,
+            TransactionConfirmationStatus::Confirmed => Commitment::Confirmed,
+            TransactionConfirmationStatus::Finalized => Commitment::Finalized,
+        }
+    }
+}
+
+/// Signature status agreed across RPC endpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureConfirmation {
+    pub slot: u64,
+    pub commitment: Commitment,
+    /// Fewest confirmations among agreeing endpoints (None once rooted)
+    pub confirmations: Option<usize>,
+}
+
+/// Why a previously issued verification was withdrawn
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Revocation {
+    pub reason: String,
+    pub revoked_at: DateTime<Utc>,
+}
+
+/// Why a transaction could not be verified
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FailureReason {
+    /// The transaction executed but failed on-chain
+    TransactionFailed { error: TransactionError },
+    /// The network is halted, so nothing can be verified
+    NetworkHalted,
+    /// Too few RPC endpoints agreed on the answer
+    ConsensusShortfall {
+        agreeing: usize,
+        required: usize,
+        weight: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        required_weight: Option<f64>,
+        dissenting: Vec<String>,
+    },
+    /// RPC endpoints errored or timed out
+    RpcUnavailable { message: String },
+    /// The transaction response could not be interpreted
+    ParseFailed { message: String },
+    /// The transaction was rolled back after being verified
+    Revoked { reason: String },
+    /// The transaction was seen in `slot` but a quorum of endpoints no longer knows it
+    Dropped { slot: u64 },
+}
+
+impl FailureReason {
+    /// Whether verifying again later may succeed
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            FailureReason::NetworkHalted
+            | FailureReason::ConsensusShortfall { .. }
+            | FailureReason::RpcUnavailable { .. } => true,
+            FailureReason::TransactionFailed { .. }
+            | FailureReason::ParseFailed { .. }
+            | FailureReason::Revoked { .. }
+            | FailureReason::Dropped { .. } => false,
+        }
+    }
+}
+
+/// Overall outcome of a verification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum VerificationOutcome {
+    Verified,
+    /// Not verified, with no specific reason recorded
+    Unverified,
+    Failed {
+        reason: FailureReason,
+        retryable: bool,
+    },
+}
+
+impl From<FailureReason> for VerificationOutcome {
+    fn from(reason: FailureReason) -> Self {
+        VerificationOutcome::Failed {
+            retryable: reason.is_retryable(),
+            reason,
+        }
+    }
+}
+
+/// Complete verification result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationResult {
+    #[serde(serialize_with = "serialize_signature", deserialize_with = "deserialize_signature")]
+    pub signature: Signature,
+    pub slot: u64,
+    pub verified: bool,
+    pub outcome: VerificationOutcome,
+    pub risk_score: f64,
+    /// Weighted contribution of each risk factor to `risk_score`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub risk_breakdown: Vec<RiskContribution>,
+    pub finality_level: FinalityLevel,
+    /// Finality the transferred amount calls for, when above the default
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub required_finality: Option<FinalityLevel>,
+    /// Percent of active stake that had voted on or past `slot`, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_stake_percent: Option<f64>,
+    /// Commitment the transaction reached across endpoints
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitment: Option<Commitment>,
+    pub network_health: NetworkHealth,
+    pub consensus_count: u8,
+    pub timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parsed_transaction: Option<ParsedTransaction>,
+    /// Set once the transaction was rolled back after being verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revocation: Option<Revocation>,
+    /// Per-step record of the pipeline, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audit: Option<AuditTrail>,
+    /// Evaluation against the acceptance policy named in the request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub policy: Option<PolicyReport>,
+}
+
+// Custom serializer for Signature (as string instead of byte array)
+fn serialize_signature<S>(sig: &Signature, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&sig.to_string())
+}
+
+fn deserialize_signature<'de, D>(deserializer: D) -> Result<Signature, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let sig = String::deserialize(deserializer)?;
+    sig.parse().map_err(serde::de::Error::custom)
+}
+
+impl VerificationResult {
+    pub fn new(signature: Signature, slot: u64) -> Self {
+        Self {
+            signature,
+            slot,
+            verified: false,
+            outcome: VerificationOutcome::Unverified,
+            risk_score: 1.0,
+            risk_breakdown: Vec::new(),
+            finality_level: FinalityLevel::Fast,
+            required_finality: None,
+            confirmed_stake_percent: None,
+            commitment: None,
+            network_health: NetworkHealth::Healthy,
+            consensus_count: 0,
+            timestamp: Utc::now(),
+            parsed_transaction: None,
+            revocation: None,
+            audit: None,
+            policy: None,
+        }
+    }
+
+    pub fn with_verification(mut self, verified: bool) -> Self {
+        self.verified = verified;
+        self.outcome = if verified {
+            VerificationOutcome::Verified
+        } else {
+            VerificationOutcome::Unverified
+        };
+        self
+    }
+
+    /// Mark as not verified for the given reason
+    pub fn with_failure(mut self, reason: FailureReason) -> Self {
+        self.verified = false;
+        self.outcome = reason.into();
+        self
+    }
+
+    pub fn with_consensus(mut self, count: u8) -> Self {
+        self.consensus_count = count;
+        self
+    }
+
+    pub fn with_finality(mut self, level: FinalityLevel) -> Self {
+        self.finality_level = level;
+        self
+    }
+
+    pub fn with_required_finality(mut self, level: Option<FinalityLevel>) -> Self {
+        self.required_finality = level;
+        self
+    }
+
+    pub fn with_confirmed_stake(mut self, stake_percent: Option<f64>) -> Self {
+        self.confirmed_stake_percent = stake_percent;
+        self
+    }
+
+    pub fn with_commitment(mut self, commitment: Commitment) -> Self {
+        self.commitment = Some(commitment);
+        self
+    }
+
+    pub fn with_network_health(mut self, health: NetworkHealth) -> Self {
+        self.network_health = health;
+        self
+    }
+
+    pub fn with_risk_score(mut self, score: f64) -> Self {
+        self.risk_score = score.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn with_risk_breakdown(mut self, breakdown: Vec<RiskContribution>) -> Self {
+        self.risk_breakdown = breakdown;
+        self
+    }
+
+    pub fn with_parsed_transaction(mut self, parsed: Option<ParsedTransaction>) -> Self {
+        self.parsed_transaction = parsed;
+        self
+    }
+
+    pub fn with_audit(mut self, audit: AuditTrail) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Evaluate against a named policy and attach the report
+    pub fn with_policy(mut self, name: &str, policy: &AcceptancePolicy) -> Self {
+        self.policy = Some(policy.evaluate(name, &self));
+        self
+    }
+
+    /// Withdraw a verification whose transaction was rolled back
+    pub fn revoke(mut self, reason: impl Into<String>) -> Self {
+        let reason = reason.into();
+        self.risk_score = 1.0;
+        self.revocation = Some(Revocation {
+            reason: reason.clone(),
+            revoked_at: Utc::now(),
+        });
+        self.with_failure(FailureReason::Revoked { reason })
+    }
+
+    /// Retryable failures may verify on a later attempt
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.outcome, VerificationOutcome::Failed { retryable: true, .. })
+    }
+
+    /// Accepted by the configured standard policy (see `Config::standard_policy`)
+    pub fn is_safe(&self, standard: &AcceptancePolicy) -> bool {
+        standard.evaluate("standard", self).accepted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_finality_levels() {
+        assert_eq!(FinalityLevel::Fast.required_stake_percent(), 66.0);
+        assert_eq!(FinalityLevel::from_stake_percent(95.0), FinalityLevel::UltraSafe);
+        assert!(FinalityLevel::UltraSafe > FinalityLevel::Fast);
+    }
+
+    #[test]
+    fn test_commitment_order() {
+        assert!(Commitment::Finalized > Commitment::Confirmed);
+        assert!(Commitment::Confirmed > Commitment::Processed);
+        assert_eq!(
+            Commitment::from(TransactionConfirmationStatus::Confirmed),
+            Commitment::Confirmed
+        );
+    }
+
+    #[test]
+    fn test_failure_outcome_serialization() {
+        let result = VerificationResult::new(Signature::default(), 12345).with_failure(
+            FailureReason::TransactionFailed {
+                error: TransactionError::InsufficientFundsForFee,
+            },
+        );
+        assert!(!result.verified);
+        assert!(!result.is_retryable());
+
+        let json = serde_json::to_value(&result.outcome).unwrap();
+        assert_eq!(json["status"], "failed");
+        assert_eq!(json["reason"]["kind"], "transaction_failed");
+        assert_eq!(json["retryable"], false);
+
+        let halted = VerificationOutcome::from(FailureReason::NetworkHalted);
+        assert_eq!(
+            halted,
+            VerificationOutcome::Failed { reason: FailureReason::NetworkHalted, retryable: true }
+        );
+    }
+
+    #[test]
+    fn test_verification_result_builder() {
+        let sig = Signature::from_str(
+            "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+        ).unwrap();
+        
+        let result = VerificationResult::new(sig, 12345)
+            .with_verification(true)
+            .with_consensus(4)
+            .with_finality(FinalityLevel::UltraSafe)
+            .with_risk_score(0.05);
+
+        let standard = AcceptancePolicy::standard();
+        assert!(result.is_safe(&standard));
+
+        let large = result.clone().with_finality(FinalityLevel::Safe);
+        assert!(large.is_safe(&standard));
+        assert!(!large.with_required_finality(Some(FinalityLevel::UltraSafe)).is_safe(&standard));
+
+        let revoked = result.revoke("slot orphaned");
+        assert!(!revoked.is_safe(&standard));
+        assert!(!revoked.is_retryable());
+        assert_eq!(revoked.revocation.unwrap().reason, "slot orphaned");
+    }
 }
\ No newline at end of file
@@ -0,0 +1,92 @@
+use solana_sdk::signature::Signature;
+use std::collections::{HashMap, VecDeque};
+use tokio::sync::RwLock;
+
+use crate::rpc::ConsensusOutcome;
+use crate::types::{AuditTrail, EndpointAnswer, EndpointVerdict};
+
+/// Latest audit trail per signature, bounded to the most recent verifications
+pub struct AuditLog {
+    inner: RwLock<AuditLogInner>,
+    capacity: usize,
+}
+
+struct AuditLogInner {
+    trails: HashMap<Signature, AuditTrail>,
+    /// Insertion order, oldest first, for eviction
+    order: VecDeque<Signature>,
+}
+
+impl AuditLog {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: RwLock::new(AuditLogInner {
+                trails: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+            capacity,
+        }
+    }
+
+    /// Store a trail, replacing any earlier one for the same signature
+    pub async fn record(&self, signature: Signature, trail: AuditTrail) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut inner = self.inner.write().await;
+        if inner.trails.insert(signature, trail).is_none() {
+            inner.order.push_back(signature);
+        }
+
+        while inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.trails.remove(&oldest);
+            }
+        }
+    }
+
+    pub async fn get(&self, signature: &Signature) -> Option<AuditTrail> {
+        self.inner.read().await.trails.get(signature).cloned()
+    }
+}
+
+/// Per-endpoint verdicts of a consensus query, in agreeing/dissenting/failed order
+pub(crate) fn endpoint_answers<T>(outcome: &ConsensusOutcome<T>) -> Vec<EndpointAnswer> {
+    let verdicts = outcome
+        .agreeing
+        .iter()
+        .map(|endpoint| (endpoint, EndpointVerdict::Agreed))
+        .chain(outcome.dissenting.iter().map(|endpoint| (endpoint, EndpointVerdict::Dissented)))
+        .chain(outcome.failed.iter().map(|endpoint| (endpoint, EndpointVerdict::Failed)));
+
+    verdicts
+        .map(|(endpoint, verdict)| EndpointAnswer {
+            endpoint: endpoint.clone(),
+            verdict,
+            latency_ms: outcome
+                .latencies
+                .get(endpoint)
+                .map(|latency| latency.as_millis() as u64),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_audit_log_evicts_oldest() {
+        let log = AuditLog::new(2);
+        let signatures: Vec<Signature> = (0..3).map(|_| Signature::new_unique()).collect();
+
+        for signature in &signatures {
+            log.record(*signature, AuditTrail::new()).await;
+        }
+
+        assert!(log.get(&signatures[0]).await.is_none());
+        assert!(log.get(&signatures[1]).await.is_some());
+        assert!(log.get(&signatures[2]).await.is_some());
+    }
+}
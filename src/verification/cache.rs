@@ -0,0 +1,189 @@
+use serde::Serialize;
+use solana_sdk::signature::Signature;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::time::Instant;
+
+use crate::types::{Commitment, VerificationResult};
+
+/// Cache hit/miss counters and current size
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    /// Finalized results, which never expire
+    pub finalized_entries: usize,
+}
+
+/// Recent verification results by signature
+///
+/// Finalized results are kept until evicted. Anything else (Fast or Safe
+/// results, on-chain failures) expires after `ttl`, since its finality can
+/// still advance or be rolled back. Retryable failures and revocations are
+/// never cached.
+pub struct ResultCache {
+    inner: RwLock<CacheInner>,
+    capacity: usize,
+    ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+struct CacheInner {
+    entries: HashMap<Signature, CacheEntry>,
+    /// Insertion order, oldest first, for eviction
+    order: VecDeque<Signature>,
+}
+
+struct CacheEntry {
+    result: VerificationResult,
+    /// None for finalized results
+    expires_at: Option<Instant>,
+}
+
+impl CacheEntry {
+    fn is_live(&self, now: Instant) -> bool {
+        self.expires_at.is_none_or(|expires_at| now < expires_at)
+    }
+}
+
+impl ResultCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            inner: RwLock::new(CacheInner {
+                entries: HashMap::new(),
+                order: VecDeque::new(),
+            }),
+            capacity,
+            ttl,
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Cached result, if present and not expired
+    pub async fn get(&self, signature: &Signature) -> Option<VerificationResult> {
+        let cached = {
+            let inner = self.inner.read().await;
+            inner
+                .entries
+                .get(signature)
+                .filter(|entry| entry.is_live(Instant::now()))
+                .map(|entry| entry.result.clone())
+        };
+
+        let counter = if cached.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        cached
+    }
+
+    /// Store a result, replacing any earlier one for the same signature
+    pub async fn insert(&self, result: VerificationResult) {
+        if self.capacity == 0 || result.is_retryable() || result.revocation.is_some() {
+            return;
+        }
+
+        let expires_at = if result.verified && result.commitment == Some(Commitment::Finalized) {
+            None
+        } else {
+            Some(Instant::now() + self.ttl)
+        };
+
+        let signature = result.signature;
+        let mut inner = self.inner.write().await;
+        let entry = CacheEntry { result, expires_at };
+        if inner.entries.insert(signature, entry).is_none() {
+            inner.order.push_back(signature);
+        }
+
+        while inner.order.len() > self.capacity {
+            if let Some(oldest) = inner.order.pop_front() {
+                inner.entries.remove(&oldest);
+            }
+        }
+    }
+
+    /// Replace a cached result with a newer one (e.g. now finalized), if cached
+    pub async fn upgrade(&self, result: VerificationResult) {
+        if self.inner.read().await.entries.contains_key(&result.signature) {
+            self.insert(result).await;
+        }
+    }
+
+    /// Drop a cached result, e.g. after its transaction was rolled back
+    pub async fn invalidate(&self, signature: &Signature) {
+        let mut inner = self.inner.write().await;
+        if inner.entries.remove(signature).is_some() {
+            inner.order.retain(|cached| cached != signature);
+        }
+    }
+
+    pub async fn stats(&self) -> CacheStats {
+        let inner = self.inner.read().await;
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: inner.entries.len(),
+            finalized_entries: inner
+                .entries
+                .values()
+                .filter(|entry| entry.expires_at.is_none())
+                .count(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{FailureReason, FinalityLevel};
+
+    fn verified(signature: Signature, commitment: Commitment) -> VerificationResult {
+        VerificationResult::new(signature, 1000)
+            .with_verification(true)
+            .with_commitment(commitment)
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_unfinalized_results_expire() {
+        let cache = ResultCache::new(10, Duration::from_millis(20));
+        let confirmed = Signature::new_unique();
+        let finalized = Signature::new_unique();
+
+        cache.insert(verified(confirmed, Commitment::Confirmed)).await;
+        cache.insert(verified(finalized, Commitment::Finalized)).await;
+        assert!(cache.get(&confirmed).await.is_some());
+
+        tokio::time::advance(Duration::from_millis(30)).await;
+        assert!(cache.get(&confirmed).await.is_none());
+        assert!(cache.get(&finalized).await.is_some());
+
+        let stats = cache.stats().await;
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert_eq!(stats.finalized_entries, 1);
+    }
+
+    #[tokio::test]
+    async fn test_upgrade_and_skip_retryable() {
+        let cache = ResultCache::new(10, Duration::from_secs(60));
+        let signature = Signature::new_unique();
+
+        cache.upgrade(verified(signature, Commitment::Finalized)).await;
+        assert!(cache.get(&signature).await.is_none());
+
+        cache.insert(verified(signature, Commitment::Confirmed)).await;
+        cache
+            .upgrade(verified(signature, Commitment::Finalized).with_finality(FinalityLevel::UltraSafe))
+            .await;
+        let cached = cache.get(&signature).await.unwrap();
+        assert_eq!(cached.finality_level, FinalityLevel::UltraSafe);
+
+        let halted = VerificationResult::new(Signature::new_unique(), 0)
+            .with_failure(FailureReason::NetworkHalted);
+        cache.insert(halted.clone()).await;
+        assert!(cache.get(&halted.signature).await.is_none());
+    }
+}
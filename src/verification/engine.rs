@@ -1,274 +1,1076 @@
-use solana_sdk::signature::Signature;
-use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
-use std::sync::Arc;
-use tracing::{debug, info, warn};
-
-use crate::error::{Result, StauroXError};
-use crate::monitor::HealthMonitor;
-use crate::parsers::TransactionParser;
-use crate::rpc::MultiRpcClient;
-use crate::types::{FinalityLevel, NetworkHealth, VerificationResult};
-
-use super::finality::FinalityChecker;
-use super::risk::RiskScorer;
-
-/// Main verification engine - orchestrates the complete verification pipeline
-pub struct VerificationEngine {
-    rpc_client: Arc<MultiRpcClient>,
-    pub health_monitor: Arc<HealthMonitor>,
-    _finality_checker: FinalityChecker,
-    risk_scorer: RiskScorer,
-    parser: TransactionParser,
-}
-
-impl VerificationEngine {
-    pub fn new(
-        rpc_client: Arc<MultiRpcClient>,
-        health_monitor: Arc<HealthMonitor>,
-    ) -> Self {
-        Self {
-            rpc_client,
-            health_monitor,
-            _finality_checker: FinalityChecker::new(),
-            risk_scorer: RiskScorer::new(),
-            parser: TransactionParser::new(),
-        }
-    }
-
-    /// Main verification entry point
-    /// 
-    /// Verification Pipeline:
-    /// 1. Check network health (refuse if halted)
-    /// 2. Fetch transaction from multiple RPCs with consensus
-    /// 3. Parse bridge transaction (if applicable)
-    /// 4. Verify transaction succeeded on-chain
-    /// 5. Determine finality level based on slot age
-    /// 6. Calculate risk score
-    /// 7. Return verification result
-    pub async fn verify_transaction(
-        &self,
-        signature: &Signature,
-    ) -> Result<VerificationResult> {
-        info!("Starting verification for: {}", signature);
-
-        // Step 1: Network Health Check
-        let network_health = self.check_network_health().await?;
-        
-        // Step 2: Fetch Transaction with Consensus
-        let (tx, consensus_count) = self.fetch_transaction_with_metadata(signature).await?;
-        
-        // Step 2.5: Parse bridge transaction (NEW)
-        info!("Attempting to parse bridge transaction...");
-        let parsed_tx = match self.parser.parse_transaction(&tx) {
-            Ok(Some(parsed)) => {
-                info!(
-                    "✓ Parsed {} bridge: amount={:?}, target_chain={:?}",
-                    parsed.bridge_name(),
-                    parsed.amount(),
-                    parsed.target_chain()
-                );
-                Some(parsed)
-            }
-            Ok(None) => {
-                info!("Not a bridge transaction");
-                None
-            }
-            Err(e) => {
-                warn!("Failed to parse transaction: {}", e);
-                None
-            }
-        };
-        
-        // Step 3: Verify Transaction Success
-        let tx_success = self.check_transaction_success(&tx)?;
-        
-        if !tx_success {
-            return self.build_failed_verification_result(
-                *signature,
-                tx.slot,
-                network_health,
-                "Transaction failed on-chain",
-                None,
-            );
-        }
-
-        // Step 4: Determine Finality
-        let finality = self.determine_finality_level(tx.slot).await?;
-        
-        // Step 5: Calculate Risk Score
-        let consensus_ratio = self.calculate_consensus_ratio(consensus_count);
-        let risk_score = self.calculate_risk(finality, network_health, consensus_ratio);
-        
-        // Step 6: Build Success Result
-        let result = VerificationResult::new(*signature, tx.slot)
-            .with_verification(true)
-            .with_finality(finality)
-            .with_network_health(network_health)
-            .with_risk_score(risk_score)
-            .with_consensus(consensus_count as u8)
-            .with_parsed_transaction(parsed_tx);
-
-        info!(
-            "✓ Verification complete: slot={}, finality={:?}, risk={:.3}",
-            tx.slot, finality, risk_score
-        );
-
-        Ok(result)
-    }
-
-    /// Step 1: Check network health
-    async fn check_network_health(&self) -> Result<NetworkHealth> {
-        let health = self.health_monitor.get_health().await;
-        
-        if health == NetworkHealth::Halted {
-            warn!("Network is halted - refusing verification");
-            return Err(StauroXError::verification(
-                "Network halted - cannot verify transactions"
-            ));
-        }
-
-        debug!("Network health: {:?}", health);
-        Ok(health)
-    }
-
-    /// Step 2: Fetch transaction with consensus tracking
-    async fn fetch_transaction_with_metadata(
-        &self,
-        signature: &Signature,
-    ) -> Result<(EncodedConfirmedTransactionWithStatusMeta, usize)> {
-        let tx = self
-            .rpc_client
-            .fetch_transaction_with_consensus(signature)
-            .await?;
-
-        let consensus_count = 1;
-        
-        debug!(
-            "Transaction fetched: slot={}, consensus={}/{}",
-            tx.slot,
-            consensus_count,
-            self.rpc_client.client_count()
-        );
-
-        Ok((tx, consensus_count))
-    }
-
-    /// Step 3: Check if transaction succeeded on-chain
-    fn check_transaction_success(
-        &self,
-        tx: &EncodedConfirmedTransactionWithStatusMeta,
-    ) -> Result<bool> {
-        let success = tx.transaction.meta.as_ref()
-            .map(|meta| meta.err.is_none())
-            .unwrap_or(false);
-
-        if success {
-            debug!("Transaction succeeded on-chain");
-        } else {
-            warn!("Transaction failed on-chain");
-        }
-
-        Ok(success)
-    }
-
-    /// Step 4: Determine finality level based on slot age
-    async fn determine_finality_level(&self, tx_slot: u64) -> Result<FinalityLevel> {
-        let current_slot = self.rpc_client.get_slot_with_consensus().await?;
-        let slot_age = current_slot.saturating_sub(tx_slot);
-
-        let finality = match slot_age {
-            0..=31 => FinalityLevel::Fast,
-            32..=63 => FinalityLevel::Safe,
-            _ => FinalityLevel::UltraSafe,
-        };
-
-        debug!(
-            "Finality: {:?} (slot_age={}, current={}, tx={})",
-            finality, slot_age, current_slot, tx_slot
-        );
-
-        Ok(finality)
-    }
-
-    /// Step 5: Calculate consensus ratio
-    fn calculate_consensus_ratio(&self, consensus_count: usize) -> f64 {
-        let total_rpcs = self.rpc_client.client_count();
-        consensus_count as f64 / total_rpcs as f64
-    }
-
-    /// Step 5: Calculate risk score
-    fn calculate_risk(
-        &self,
-        finality: FinalityLevel,
-        network_health: NetworkHealth,
-        consensus_ratio: f64,
-    ) -> f64 {
-        self.risk_scorer.calculate_risk(finality, network_health, consensus_ratio)
-    }
-
-    /// Build result for failed verification
-    fn build_failed_verification_result(
-        &self,
-        signature: Signature,
-        slot: u64,
-        network_health: NetworkHealth,
-        reason: &str,
-        parsed_tx: Option<crate::parsers::ParsedTransaction>,
-    ) -> Result<VerificationResult> {
-        warn!("Verification failed: {}", reason);
-        
-        Ok(VerificationResult::new(signature, slot)
-            .with_verification(false)
-            .with_finality(FinalityLevel::Fast)
-            .with_network_health(network_health)
-            .with_risk_score(1.0)
-            .with_consensus(0u8)
-            .with_parsed_transaction(parsed_tx))
-    }
-
-    /// Batch verify multiple transactions
-    pub async fn verify_batch(
-        &self,
-        signatures: &[Signature],
-    ) -> Vec<Result<VerificationResult>> {
-        let mut results = Vec::with_capacity(signatures.len());
-
-        for sig in signatures {
-            results.push(self.verify_transaction(sig).await);
-        }
-
-        results
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::config::Config;
-    use crate::service::VerificationService;
-
-    #[test]
-    fn test_engine_creation() {
-        let config = Config::mainnet();
-        let service = VerificationService::new(config).unwrap();
-        
-        let _engine = VerificationEngine::new(
-            service.rpc_client(),
-            service.health_monitor(),
-        );
-    }
-
-    #[test]
-    fn test_consensus_ratio_calculation() {
-        let config = Config::mainnet();
-        let service = VerificationService::new(config).unwrap();
-        
-        let engine = VerificationEngine::new(
-            service.rpc_client(),
-            service.health_monitor(),
-        );
-
-        assert_eq!(engine.calculate_consensus_ratio(2), 1.0);
-        assert_eq!(engine.calculate_consensus_ratio(1), 0.5);
-    }
-}
\ No newline at end of file
+use futures::stream::{self, StreamExt};
+use solana_sdk::signature::Signature;
+use solana_transaction_status::EncodedConfirmedTransactionWithStatusMeta;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{debug, info, warn};
+
+use crate::config::AmountTiers;
+use crate::error::{Result, StauroXError};
+use crate::monitor::{HealthMonitor, StakeMonitor};
+use crate::parsers::{ParsedTransaction, TransactionParser};
+use crate::rpc::{ConsensusOutcome, MultiRpcClient, SignatureRetention};
+use crate::storage::{AsyncStore, VerificationStore};
+use crate::types::{
+    AuditStep, AuditTrail, FailureReason, FinalityLevel, NetworkHealth, SignatureConfirmation,
+    VerificationResult,
+};
+
+use super::audit::{endpoint_answers, AuditLog};
+use super::cache::{CacheStats, ResultCache};
+use super::finality::FinalityChecker;
+use super::risk::{RiskContext, RiskScorer};
+use super::watch::ReorgWatch;
+
+/// How often a wait-for-finality request re-checks the transaction
+const FINALITY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Concurrent transaction fetches in `verify_batch` unless configured
+const DEFAULT_BATCH_CONCURRENCY: usize = 16;
+
+/// Transaction that was fetched and succeeded on-chain, awaiting finality
+struct PendingVerification {
+    signature: Signature,
+    slot: u64,
+    consensus_count: usize,
+    parsed_tx: Option<ParsedTransaction>,
+}
+
+/// Result of fetching and checking a transaction
+enum Fetched {
+    Pending(PendingVerification),
+    /// Failed on-chain; the result is final
+    Failed(Box<VerificationResult>),
+}
+
+/// Main verification engine - orchestrates the complete verification pipeline
+pub struct VerificationEngine {
+    rpc_client: Arc<MultiRpcClient>,
+    pub health_monitor: Arc<HealthMonitor>,
+    stake_monitor: Option<Arc<StakeMonitor>>,
+    reorg_watch: Option<Arc<ReorgWatch>>,
+    audit_log: Option<Arc<AuditLog>>,
+    cache: Option<Arc<ResultCache>>,
+    store: Option<AsyncStore>,
+    finality_checker: FinalityChecker,
+    risk_scorer: RiskScorer,
+    amount_tiers: AmountTiers,
+    parser: TransactionParser,
+    batch_concurrency: usize,
+}
+
+impl VerificationEngine {
+    pub fn new(
+        rpc_client: Arc<MultiRpcClient>,
+        health_monitor: Arc<HealthMonitor>,
+    ) -> Self {
+        Self {
+            rpc_client,
+            health_monitor,
+            stake_monitor: None,
+            reorg_watch: None,
+            audit_log: None,
+            cache: None,
+            store: None,
+            finality_checker: FinalityChecker::new(),
+            risk_scorer: RiskScorer::new(),
+            amount_tiers: AmountTiers::default(),
+            parser: TransactionParser::new(),
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+        }
+    }
+
+    /// Use vote account stake to grade confirmed (not yet finalized) transactions
+    pub fn with_stake_monitor(mut self, stake_monitor: Arc<StakeMonitor>) -> Self {
+        self.stake_monitor = Some(stake_monitor);
+        self
+    }
+
+    /// Parse with these bridge parsers instead of the built-in mainnet ones
+    pub fn with_parser(mut self, parser: TransactionParser) -> Self {
+        self.parser = parser;
+        self
+    }
+
+    /// Watch verified, non-finalized results for rollbacks
+    pub fn with_reorg_watch(mut self, reorg_watch: Arc<ReorgWatch>) -> Self {
+        self.reorg_watch = Some(reorg_watch);
+        self
+    }
+
+    /// Keep each result's audit trail for later retrieval
+    pub fn with_audit_log(mut self, audit_log: Arc<AuditLog>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Serve repeat verifications from a result cache
+    pub fn with_cache(mut self, cache: Arc<ResultCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    pub async fn cache_stats(&self) -> Option<CacheStats> {
+        match &self.cache {
+            Some(cache) => Some(cache.stats().await),
+            None => None,
+        }
+    }
+
+    /// Retained revocation for the signature, else its live cached result
+    async fn cached(&self, signature: &Signature) -> Option<VerificationResult> {
+        if let Some(reorg_watch) = &self.reorg_watch {
+            if let Some(revoked) = reorg_watch.revoked(signature).await {
+                return Some(revoked);
+            }
+        }
+
+        match &self.cache {
+            Some(cache) => cache.get(signature).await,
+            None => None,
+        }
+    }
+
+    /// Persist each conclusive result
+    pub fn with_store(mut self, store: Arc<dyn VerificationStore>) -> Self {
+        self.store = Some(AsyncStore::new(store));
+        self
+    }
+
+    pub fn store(&self) -> Option<&AsyncStore> {
+        self.store.as_ref()
+    }
+
+    /// Latest audit trail recorded for a signature
+    pub async fn audit_trail(&self, signature: &Signature) -> Option<AuditTrail> {
+        match &self.audit_log {
+            Some(audit_log) => audit_log.get(signature).await,
+            None => None,
+        }
+    }
+
+    /// Score risk with custom factors or weights
+    pub fn with_risk_scorer(mut self, risk_scorer: RiskScorer) -> Self {
+        self.risk_scorer = risk_scorer;
+        self
+    }
+
+    /// Raise required finality and risk for large transfers
+    pub fn with_amount_tiers(mut self, amount_tiers: AmountTiers) -> Self {
+        self.amount_tiers = amount_tiers;
+        self
+    }
+
+    /// Cap on concurrent transaction fetches in `verify_batch`
+    pub fn with_batch_concurrency(mut self, concurrency: usize) -> Self {
+        self.batch_concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Main verification entry point
+    /// 
+    /// Verification Pipeline:
+    /// 1. Check network health (refuse if halted)
+    /// 2. Fetch transaction from multiple RPCs with consensus
+    /// 3. Verify transaction succeeded on-chain
+    /// 4. Parse bridge transaction (if applicable; parse errors are only audited)
+    /// 5. Determine finality level from signature status commitment across RPCs
+    /// 6. Calculate risk score
+    /// 7. Return verification result (watched for rollbacks until finalized)
+    ///
+    /// Halts, RPC errors and consensus shortfalls come back as an unverified
+    /// result carrying a `FailureReason` rather than as errors. Every result
+    /// carries an audit trail with one entry per step reached.
+    ///
+    /// A live cached result, or a revocation still retained by the reorg
+    /// watch, is returned without running the pipeline.
+    pub async fn verify_transaction(
+        &self,
+        signature: &Signature,
+    ) -> Result<VerificationResult> {
+        if let Some(cached) = self.cached(signature).await {
+            debug!("Serving cached or revoked verification for {}", signature);
+            return Ok(cached);
+        }
+
+        self.verify_fresh(signature).await
+    }
+
+    /// Run the full pipeline even if a cached result exists, then cache the outcome
+    pub async fn verify_fresh(&self, signature: &Signature) -> Result<VerificationResult> {
+        info!("Starting verification for: {}", signature);
+
+        let mut audit = AuditTrail::new();
+        let result = match self.run_pipeline(signature, &mut audit).await {
+            Ok(result) => result,
+            Err(e) => self.failed_result_from_error(*signature, 0, e).await?,
+        };
+
+        Ok(self.finish_result(result, audit).await)
+    }
+
+    /// Replace the cached and stored verdict with a fresh one
+    ///
+    /// Used once a watched transaction finalizes: finality, stake and risk
+    /// are all re-graded rather than patched onto the earlier result.
+    pub async fn reverify(&self, signature: &Signature) -> Result<VerificationResult> {
+        if let Some(cache) = &self.cache {
+            cache.invalidate(signature).await;
+        }
+
+        self.verify_fresh(signature).await
+    }
+
+    async fn run_pipeline(
+        &self,
+        signature: &Signature,
+        audit: &mut AuditTrail,
+    ) -> Result<VerificationResult> {
+        // Step 1: Network Health Check
+        let network_health = self.check_network_health(audit).await?;
+
+        // Steps 2-3: Fetch, parse and check the transaction
+        let pending = match self.fetch_and_check(signature, network_health, audit).await? {
+            Fetched::Pending(pending) => pending,
+            Fetched::Failed(result) => return Ok(*result),
+        };
+
+        // Step 4: Determine Finality
+        let confirmation = self
+            .finality_checker
+            .check_finality(&self.rpc_client, signature, pending.slot)
+            .await;
+        let confirmation = self.record_finality_error(confirmation, audit)?;
+
+        // Steps 5-6: Risk score and result
+        Ok(self
+            .complete_verification(pending, confirmation, network_health, audit)
+            .await)
+    }
+
+    /// Attach the audit trail to a result, keep it in the audit log, then cache
+    /// and persist the result
+    ///
+    /// Retryable failures are not persisted so they never replace an earlier verdict.
+    async fn finish_result(&self, result: VerificationResult, audit: AuditTrail) -> VerificationResult {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.record(result.signature, audit.clone()).await;
+        }
+
+        let result = result.with_audit(audit);
+        if let Some(cache) = &self.cache {
+            cache.insert(result.clone()).await;
+        }
+        if let Some(store) = &self.store {
+            if !result.is_retryable() {
+                if let Err(e) = store.save(result.clone()).await {
+                    warn!("Failed to persist verification of {}: {}", result.signature, e);
+                }
+            }
+        }
+        result
+    }
+
+    /// Steps 2-3: fetch the transaction with consensus, check it succeeded and parse it
+    async fn fetch_and_check(
+        &self,
+        signature: &Signature,
+        network_health: NetworkHealth,
+        audit: &mut AuditTrail,
+    ) -> Result<Fetched> {
+        // Step 2: Fetch Transaction with Consensus
+        let (tx, consensus_count) = self.fetch_transaction_with_metadata(signature, audit).await?;
+
+        // Step 3: Verify Transaction Success
+        let success = self.check_transaction_success(&tx);
+        audit.record(AuditStep::ExecutionStatus {
+            succeeded: success.is_ok(),
+            error: match &success {
+                Err(FailureReason::TransactionFailed { error }) => Some(error.clone()),
+                _ => None,
+            },
+        });
+
+        // Step 3.5: Parse bridge transaction
+        let parsed_tx = self.parse_transaction(&tx, audit);
+
+        if let Err(reason) = success {
+            return Ok(Fetched::Failed(Box::new(self.build_failed_verification_result(
+                *signature,
+                tx.slot,
+                network_health,
+                reason,
+                parsed_tx,
+            ))));
+        }
+
+        Ok(Fetched::Pending(PendingVerification {
+            signature: *signature,
+            slot: tx.slot,
+            consensus_count,
+            parsed_tx,
+        }))
+    }
+
+    /// Step 3.5: parse bridge instructions
+    ///
+    /// Parsing only adds detail to the verdict: a transaction that can't be
+    /// parsed is verified like a non-bridge one, with the error in the audit trail.
+    fn parse_transaction(
+        &self,
+        tx: &EncodedConfirmedTransactionWithStatusMeta,
+        audit: &mut AuditTrail,
+    ) -> Option<ParsedTransaction> {
+        info!("Attempting to parse bridge transaction...");
+        match self.parser.parse_transaction(tx) {
+            Ok(Some(parsed)) => {
+                info!(
+                    "✓ Parsed {} bridge: amount={:?}, target_chain={:?}",
+                    parsed.bridge_name(),
+                    parsed.amount(),
+                    parsed.target_chain()
+                );
+                audit.record(AuditStep::Parse {
+                    bridge: Some(parsed.bridge_name().to_string()),
+                    error: None,
+                });
+                Some(parsed)
+            }
+            Ok(None) => {
+                info!("Not a bridge transaction");
+                audit.record(AuditStep::Parse { bridge: None, error: None });
+                None
+            }
+            Err(e) => {
+                warn!("Failed to parse transaction: {}", e);
+                audit.record(AuditStep::Parse {
+                    bridge: None,
+                    error: Some(e.to_string()),
+                });
+                None
+            }
+        }
+    }
+
+    /// Steps 5-6: grade finality, score risk and build the success result
+    async fn complete_verification(
+        &self,
+        pending: PendingVerification,
+        outcome: ConsensusOutcome<SignatureConfirmation>,
+        network_health: NetworkHealth,
+        audit: &mut AuditTrail,
+    ) -> VerificationResult {
+        let confirmation = &outcome.value;
+        let (finality, confirmed_stake) = self.grade_finality(confirmation).await;
+        let current_slot = self.health_monitor.get_current_slot().await;
+
+        audit.record(AuditStep::Finality {
+            current_slot,
+            commitment: Some(confirmation.commitment),
+            confirmations: confirmation.confirmations,
+            stake_percent: confirmed_stake,
+            finality: Some(finality),
+            endpoints: endpoint_answers(&outcome),
+            error: None,
+        });
+        
+        // Step 5: Calculate Risk Score
+        let amount_tier = pending
+            .parsed_tx
+            .as_ref()
+            .and_then(|parsed| self.amount_tiers.tier_for(parsed));
+        let context = RiskContext::new(
+            finality,
+            network_health,
+            self.calculate_consensus_ratio(pending.consensus_count),
+        )
+        .with_parsed_transaction(pending.parsed_tx.as_ref())
+        .with_slot_age(current_slot.map(|slot| slot.saturating_sub(pending.slot)))
+        .with_amount_tier(amount_tier);
+        let risk_breakdown = self.risk_scorer.breakdown(&context);
+        let risk_score = RiskScorer::total(&risk_breakdown);
+        audit.record(AuditStep::Risk {
+            components: risk_breakdown.clone(),
+            score: risk_score,
+        });
+        
+        // Step 6: Build Success Result
+        let result = VerificationResult::new(pending.signature, pending.slot)
+            .with_verification(true)
+            .with_finality(finality)
+            .with_required_finality(amount_tier.map(|tier| tier.required_finality))
+            .with_commitment(confirmation.commitment)
+            .with_confirmed_stake(confirmed_stake)
+            .with_network_health(network_health)
+            .with_risk_score(risk_score)
+            .with_risk_breakdown(risk_breakdown)
+            .with_consensus(pending.consensus_count as u8)
+            .with_parsed_transaction(pending.parsed_tx);
+
+        info!(
+            "✓ Verification complete: slot={}, commitment={:?}, finality={:?}, risk={:.3}",
+            pending.slot, confirmation.commitment, finality, risk_score
+        );
+
+        if let Some(reorg_watch) = &self.reorg_watch {
+            reorg_watch.watch(result.clone()).await;
+        }
+
+        result
+    }
+
+    /// Verify and keep re-checking until the target finality is reached
+    ///
+    /// Each re-check runs the full pipeline, so a transaction that fails
+    /// on-chain or can no longer be verified (e.g. dropped with a fork)
+    /// returns its failed result immediately. Retryable failures (RPCs
+    /// unavailable, no quorum, network halted) are polled through until the
+    /// deadline, unless the transaction was already seen and a quorum no
+    /// longer knows it, which ends the wait with `Dropped`. Gives up with
+    /// `FinalityTimeout` once `timeout` has passed, or with the last
+    /// retryable failure if the transaction was never verified. Only the first check may be answered from the cache.
+    pub async fn verify_until_finality(
+        &self,
+        signature: &Signature,
+        target: FinalityLevel,
+        timeout: Duration,
+    ) -> Result<VerificationResult> {
+        info!("Waiting up to {:?} for {} to reach {:?}", timeout, signature, target);
+
+        let deadline = Instant::now() + timeout;
+        let mut reached: Option<FinalityLevel> = None;
+        let mut seen_slot: Option<u64> = None;
+        let mut last_retryable: Option<VerificationResult> = None;
+        let mut use_cache = true;
+
+        loop {
+            let verification = if use_cache {
+                self.verify_transaction(signature).await
+            } else {
+                self.verify_fresh(signature).await
+            };
+            use_cache = false;
+
+            match verification {
+                Ok(result) if result.is_retryable() => {
+                    if let Some(slot) = seen_slot {
+                        if let Some(dropped) = self.dropped_result(signature, slot).await {
+                            return Ok(dropped);
+                        }
+                    }
+                    debug!("{} not verifiable yet, retrying: {:?}", signature, result.outcome);
+                    last_retryable = Some(result);
+                }
+                Ok(result) if !result.verified => {
+                    if reached.is_some() {
+                        warn!("{} no longer verifiable while waiting: {:?}", signature, result.outcome);
+                    }
+                    return Ok(result);
+                }
+                Ok(result) if result.finality_level >= target => return Ok(result),
+                Ok(result) => {
+                    debug!(
+                        "{} at {:?} ({:?}), waiting for {:?}",
+                        signature, result.finality_level, result.commitment, target
+                    );
+                    reached = Some(result.finality_level);
+                    seen_slot = Some(result.slot);
+                }
+                Err(e) => return Err(e),
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return match (reached, last_retryable) {
+                    (None, Some(result)) => Ok(result),
+                    (reached, _) => Err(StauroXError::finality_timeout(
+                        target,
+                        reached.unwrap_or(FinalityLevel::Fast),
+                        timeout,
+                    )),
+                };
+            }
+
+            tokio::time::sleep(FINALITY_POLL_INTERVAL.min(deadline - now)).await;
+        }
+    }
+
+    /// Failed result for a transaction seen in `slot` that a quorum no longer knows
+    ///
+    /// `None` while it is still known or the check is inconclusive. The
+    /// cached verdict is dropped; the reorg watch revokes the stored one.
+    async fn dropped_result(&self, signature: &Signature, slot: u64) -> Option<VerificationResult> {
+        match self.rpc_client.check_signature_retained(signature, slot).await {
+            Ok(SignatureRetention::Vanished) => {
+                warn!("{} dropped while waiting for finality", signature);
+                if let Some(cache) = &self.cache {
+                    cache.invalidate(signature).await;
+                }
+                let network_health = self.health_monitor.get_health().await;
+                let reason = FailureReason::Dropped { slot };
+                let result =
+                    self.build_failed_verification_result(*signature, slot, network_health, reason, None);
+                Some(result)
+            }
+            _ => None,
+        }
+    }
+
+    /// Step 1: Check network health
+    async fn check_network_health(&self, audit: &mut AuditTrail) -> Result<NetworkHealth> {
+        let health = self.health_monitor.get_health().await;
+        self.record_health(health, audit).await;
+        
+        if health == NetworkHealth::Halted {
+            warn!("Network is halted - refusing verification");
+            return Err(StauroXError::NetworkHalted);
+        }
+
+        debug!("Network health: {:?}", health);
+        Ok(health)
+    }
+
+    /// Snapshot of network health for the audit trail
+    async fn record_health(&self, health: NetworkHealth, audit: &mut AuditTrail) {
+        audit.record(AuditStep::NetworkHealth {
+            health,
+            fork: self.health_monitor.get_fork_report().await,
+            lagging: self.health_monitor.get_lagging_sources().await,
+        });
+    }
+
+    /// Step 2: Fetch transaction with consensus tracking
+    ///
+    /// Returns the agreed transaction and the number of endpoints that
+    /// returned identical content for it.
+    async fn fetch_transaction_with_metadata(
+        &self,
+        signature: &Signature,
+        audit: &mut AuditTrail,
+    ) -> Result<(EncodedConfirmedTransactionWithStatusMeta, usize)> {
+        let outcome = match self.rpc_client.fetch_transaction_with_consensus(signature).await {
+            Ok(outcome) => outcome,
+            Err(e) => {
+                audit.record(AuditStep::TransactionFetch {
+                    slot: None,
+                    endpoints: vec![],
+                    error: Some(e.to_string()),
+                });
+                return Err(e);
+            }
+        };
+
+        audit.record(AuditStep::TransactionFetch {
+            slot: Some(outcome.value.slot),
+            endpoints: endpoint_answers(&outcome),
+            error: None,
+        });
+
+        let consensus_count = outcome.agreeing.len();
+        
+        debug!(
+            "Transaction fetched: slot={}, consensus={}/{} (dissenting={:?}, failed={:?})",
+            outcome.value.slot,
+            consensus_count,
+            self.rpc_client.client_count(),
+            outcome.dissenting,
+            outcome.failed
+        );
+
+        Ok((outcome.value, consensus_count))
+    }
+
+    /// Step 3: Check if transaction succeeded on-chain
+    fn check_transaction_success(
+        &self,
+        tx: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> std::result::Result<(), FailureReason> {
+        let Some(meta) = tx.transaction.meta.as_ref() else {
+            // Status may be served on a later fetch, so this must not become final
+            warn!("Transaction has no status metadata");
+            return Err(FailureReason::RpcUnavailable {
+                message: "Transaction returned without status metadata".to_string(),
+            });
+        };
+
+        match &meta.err {
+            None => {
+                debug!("Transaction succeeded on-chain");
+                Ok(())
+            }
+            Some(error) => {
+                warn!("Transaction failed on-chain: {}", error);
+                Err(FailureReason::TransactionFailed { error: error.clone() })
+            }
+        }
+    }
+
+    /// Record a failed signature status lookup in the audit trail
+    fn record_finality_error<T>(&self, lookup: Result<T>, audit: &mut AuditTrail) -> Result<T> {
+        if let Err(e) = &lookup {
+            audit.record(AuditStep::Finality {
+                current_slot: None,
+                commitment: None,
+                confirmations: None,
+                stake_percent: None,
+                finality: None,
+                endpoints: vec![],
+                error: Some(e.to_string()),
+            });
+        }
+        lookup
+    }
+
+    /// Step 4: Grade finality from the agreed signature status
+    ///
+    /// Confirmed transactions are graded further by the share of stake that
+    /// has voted past their slot, when a vote snapshot is available.
+    async fn grade_finality(&self, confirmation: &SignatureConfirmation) -> (FinalityLevel, Option<f64>) {
+        let stake_percent = match &self.stake_monitor {
+            Some(stake_monitor) => stake_monitor.stake_percent_at(confirmation.slot).await,
+            None => None,
+        };
+
+        let finality = self.finality_checker.finality_level(confirmation, stake_percent);
+
+        debug!(
+            "Finality: {:?} (commitment={:?}, confirmations={:?}, stake={:?})",
+            finality, confirmation.commitment, confirmation.confirmations, stake_percent
+        );
+
+        (finality, stake_percent)
+    }
+
+    /// Step 5: Calculate consensus ratio (agreeing endpoints over all configured endpoints)
+    fn calculate_consensus_ratio(&self, consensus_count: usize) -> f64 {
+        let total_rpcs = self.rpc_client.client_count();
+        consensus_count as f64 / total_rpcs as f64
+    }
+
+    /// Build result for failed verification
+    fn build_failed_verification_result(
+        &self,
+        signature: Signature,
+        slot: u64,
+        network_health: NetworkHealth,
+        reason: FailureReason,
+        parsed_tx: Option<ParsedTransaction>,
+    ) -> VerificationResult {
+        warn!("Verification failed: {:?}", reason);
+        
+        VerificationResult::new(signature, slot)
+            .with_failure(reason)
+            .with_finality(FinalityLevel::Fast)
+            .with_network_health(network_health)
+            .with_risk_score(1.0)
+            .with_consensus(0u8)
+            .with_parsed_transaction(parsed_tx)
+    }
+
+    /// Turn a pipeline error into a failed result when it has a known reason
+    ///
+    /// `slot` is 0 when the transaction was never fetched. Errors without a
+    /// matching `FailureReason` are passed through.
+    async fn failed_result_from_error(
+        &self,
+        signature: Signature,
+        slot: u64,
+        error: StauroXError,
+    ) -> Result<VerificationResult> {
+        let reason = match error {
+            StauroXError::NetworkHalted => FailureReason::NetworkHalted,
+            StauroXError::ConsensusFailure {
+                responses,
+                required,
+                weight,
+                required_weight,
+                dissenting,
+                ..
+            } => FailureReason::ConsensusShortfall {
+                agreeing: responses,
+                required,
+                weight,
+                required_weight,
+                dissenting,
+            },
+            e @ (StauroXError::Rpc(_) | StauroXError::RpcTimeout { .. }) => {
+                FailureReason::RpcUnavailable { message: e.to_string() }
+            }
+            e => return Err(e),
+        };
+
+        let network_health = self.health_monitor.get_health().await;
+        Ok(self.build_failed_verification_result(signature, slot, network_health, reason, None))
+    }
+
+    /// Batch verify multiple transactions
+    ///
+    /// Transactions are fetched concurrently (bounded by the batch
+    /// concurrency). Network health is read once and signature statuses are
+    /// looked up in one batched request for the whole set. Results keep the
+    /// order of `signatures`; ones with a live cached result skip the pipeline.
+    pub async fn verify_batch(
+        &self,
+        signatures: &[Signature],
+    ) -> Vec<Result<VerificationResult>> {
+        let mut cached = Vec::with_capacity(signatures.len());
+        for signature in signatures {
+            cached.push(self.cached(signature).await);
+        }
+
+        let misses: Vec<Signature> = signatures
+            .iter()
+            .zip(&cached)
+            .filter(|(_, cached)| cached.is_none())
+            .map(|(signature, _)| *signature)
+            .collect();
+        debug!("{} of {} batch signatures cached", signatures.len() - misses.len(), signatures.len());
+
+        let mut fresh = self.verify_batch_fresh(&misses).await.into_iter();
+        cached
+            .into_iter()
+            .map(|cached| match cached {
+                Some(result) => Ok(result),
+                None => fresh
+                    .next()
+                    .unwrap_or_else(|| Err(StauroXError::verification("Missing batch result"))),
+            })
+            .collect()
+    }
+
+    /// Batch verification that ignores cached results (fresh ones are cached)
+    pub async fn verify_batch_fresh(
+        &self,
+        signatures: &[Signature],
+    ) -> Vec<Result<VerificationResult>> {
+        info!(
+            "Starting batch verification of {} signatures (concurrency {})",
+            signatures.len(),
+            self.batch_concurrency
+        );
+
+        // Step 1: Network Health Check, shared across the batch
+        let network_health = self.health_monitor.get_health().await;
+        let mut health_audit = AuditTrail::new();
+        self.record_health(network_health, &mut health_audit).await;
+
+        if network_health == NetworkHealth::Halted {
+            warn!("Network is halted - refusing batch verification");
+            let mut results = Vec::with_capacity(signatures.len());
+            for signature in signatures {
+                let result = self.build_failed_verification_result(
+                    *signature,
+                    0,
+                    network_health,
+                    FailureReason::NetworkHalted,
+                    None,
+                );
+                results.push(Ok(self.finish_result(result, health_audit.clone()).await));
+            }
+            return results;
+        }
+
+        // Steps 2-3: Fetch and check each transaction concurrently
+        let fetched: Vec<(AuditTrail, Result<Fetched>)> = stream::iter(signatures.iter().copied())
+            .map(|signature| {
+                let mut audit = health_audit.clone();
+                async move {
+                    let fetched = self.fetch_and_check(&signature, network_health, &mut audit).await;
+                    (audit, fetched)
+                }
+            })
+            .buffered(self.batch_concurrency)
+            .collect()
+            .await;
+
+        // Step 4: One batched signature status lookup for every pending transaction
+        let pending: Vec<(Signature, u64)> = fetched
+            .iter()
+            .filter_map(|(_, fetched)| match fetched {
+                Ok(Fetched::Pending(pending)) => Some((pending.signature, pending.slot)),
+                _ => None,
+            })
+            .collect();
+        let mut confirmations = self
+            .finality_checker
+            .check_finality_batch(&self.rpc_client, &pending)
+            .await
+            .into_iter();
+
+        // Steps 5-6 per transaction
+        let mut results = Vec::with_capacity(signatures.len());
+        for (signature, (mut audit, fetched)) in signatures.iter().zip(fetched) {
+            let result = match fetched {
+                Ok(Fetched::Pending(pending)) => {
+                    let confirmation = confirmations
+                        .next()
+                        .unwrap_or_else(|| Err(StauroXError::verification("Missing signature status")));
+                    let slot = pending.slot;
+                    match self.record_finality_error(confirmation, &mut audit) {
+                        Ok(confirmation) => Ok(self
+                            .complete_verification(pending, confirmation, network_health, &mut audit)
+                            .await),
+                        Err(e) => self.failed_result_from_error(*signature, slot, e).await,
+                    }
+                }
+                Ok(Fetched::Failed(result)) => Ok(*result),
+                Err(e) => self.failed_result_from_error(*signature, 0, e).await,
+            };
+
+            results.push(match result {
+                Ok(result) => Ok(self.finish_result(result, audit).await),
+                Err(e) => Err(e),
+            });
+        }
+
+        results
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::rpc::{mock_rpc, mock_server, sample_transaction, signature_status};
+    use crate::service::VerificationService;
+    use crate::types::{AcceptancePolicy, VerificationOutcome};
+    use serde_json::json;
+
+    /// Engine over mock endpoints, one per transaction response, each placing
+    /// the transaction at slot 100 with the given signature status
+    async fn mock_engine(
+        transactions: &[serde_json::Value],
+        commitment: &str,
+        threshold: usize,
+    ) -> (VerificationEngine, Vec<mockito::ServerGuard>) {
+        let mut servers = Vec::new();
+        for tx in transactions {
+            let mut server = mock_server().await;
+            mock_rpc(&mut server, "getTransaction", tx.clone()).await;
+            mock_rpc(&mut server, "getSignatureStatuses", signature_status(100, commitment)).await;
+            servers.push(server);
+        }
+
+        let urls = servers.iter().map(|server| server.url()).collect();
+        let rpc_client = MultiRpcClient::new(urls, threshold, Duration::from_secs(2));
+        let engine = VerificationEngine::new(Arc::new(rpc_client), Arc::new(HealthMonitor::default()));
+        (engine, servers)
+    }
+
+    #[test]
+    fn test_engine_creation() {
+        let config = Config::mainnet();
+        let service = VerificationService::new(config).unwrap();
+        
+        let _engine = VerificationEngine::new(
+            service.rpc_client(),
+            service.health_monitor(),
+        );
+    }
+
+    #[test]
+    fn test_consensus_ratio_calculation() {
+        let config = Config::mainnet();
+        let service = VerificationService::new(config).unwrap();
+        
+        let engine = VerificationEngine::new(
+            service.rpc_client(),
+            service.health_monitor(),
+        );
+
+        assert_eq!(engine.calculate_consensus_ratio(2), 1.0);
+        assert_eq!(engine.calculate_consensus_ratio(1), 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_verify_batch_reports_each_signature() {
+        let mut config = Config::custom(
+            crate::config::Network::Devnet,
+            vec!["http://10.255.255.1:8899".to_string()],
+        );
+        config.rpc.request_timeout_ms = 100;
+        let service = VerificationService::new(config).unwrap();
+
+        let engine = VerificationEngine::new(service.rpc_client(), service.health_monitor())
+            .with_batch_concurrency(2);
+
+        let signatures = vec![Signature::new_unique(), Signature::new_unique(), Signature::new_unique()];
+        let results = engine.verify_batch(&signatures).await;
+
+        // Unreachable RPCs come back as retryable failures, not errors
+        assert_eq!(results.len(), 3);
+        for (signature, result) in signatures.iter().zip(results) {
+            let result = result.unwrap();
+            assert_eq!(result.signature, *signature);
+            assert!(!result.verified);
+            assert!(result.is_retryable());
+
+            // Health snapshot, then the failed fetch
+            let audit = result.audit.unwrap();
+            assert!(matches!(audit.entries[0].step, AuditStep::NetworkHealth { .. }));
+            assert!(matches!(
+                audit.entries[1].step,
+                AuditStep::TransactionFetch { error: Some(_), .. }
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consensus_counts_every_agreeing_endpoint() {
+        // Three endpoints agree, one serves different balances; quorum is two
+        let transactions: Vec<_> = [995_000, 995_000, 995_000, 990_000]
+            .into_iter()
+            .map(|post_balance| json!(sample_transaction(100, "2", post_balance)))
+            .collect();
+        let (engine, _servers) = mock_engine(&transactions, "finalized", 2).await;
+
+        let result = engine.verify_transaction(&Signature::new_unique()).await.unwrap();
+        assert!(result.verified);
+        assert_eq!(result.consensus_count, 3);
+        assert_eq!(engine.calculate_consensus_ratio(3), 0.75);
+
+        let consensus = result
+            .risk_breakdown
+            .iter()
+            .find(|component| component.component == "consensus")
+            .unwrap();
+        assert!((consensus.contribution - consensus.weight * 0.25).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_finality_retries_transient_failures() {
+        let mut server = mock_server().await;
+        let unavailable = server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({ "method": "getTransaction" })))
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        mock_rpc(&mut server, "getTransaction", json!(sample_transaction(100, "2", 995_000))).await;
+        mock_rpc(&mut server, "getSignatureStatuses", signature_status(100, "finalized")).await;
+
+        let rpc_client = MultiRpcClient::new(vec![server.url()], 1, Duration::from_secs(2));
+        let engine = VerificationEngine::new(Arc::new(rpc_client), Arc::new(HealthMonitor::default()));
+
+        let result = engine
+            .verify_until_finality(&Signature::new_unique(), FinalityLevel::UltraSafe, Duration::from_secs(10))
+            .await
+            .unwrap();
+
+        unavailable.assert_async().await;
+        assert!(result.verified);
+        assert_eq!(result.finality_level, FinalityLevel::UltraSafe);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_finality_ends_when_transaction_drops() {
+        let mut server = mock_server().await;
+        for (method, result) in [
+            ("getTransaction", json!(sample_transaction(100, "2", 995_000))),
+            ("getSignatureStatuses", signature_status(100, "confirmed")),
+        ] {
+            server
+                .mock("POST", "/")
+                .match_body(mockito::Matcher::PartialJson(json!({ "method": method })))
+                .with_body(json!({ "jsonrpc": "2.0", "id": 1, "result": result }).to_string())
+                .expect(1)
+                .create_async()
+                .await;
+        }
+        // Gone from then on
+        mock_rpc(&mut server, "getTransaction", json!(null)).await;
+        let unknown = json!({ "context": { "slot": 104 }, "value": [null] });
+        mock_rpc(&mut server, "getSignatureStatuses", unknown).await;
+
+        let rpc_client = MultiRpcClient::new(vec![server.url()], 1, Duration::from_secs(2));
+        let engine = VerificationEngine::new(Arc::new(rpc_client), Arc::new(HealthMonitor::default()));
+
+        let started = Instant::now();
+        let result = engine
+            .verify_until_finality(&Signature::new_unique(), FinalityLevel::UltraSafe, Duration::from_secs(30))
+            .await
+            .unwrap();
+
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(matches!(
+            result.outcome,
+            VerificationOutcome::Failed { reason: FailureReason::Dropped { slot: 100 }, .. }
+        ));
+        assert!(!result.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_parse_errors_do_not_fail_verification() {
+        let mut tx = json!(sample_transaction(100, "2", 995_000));
+        tx["transaction"]["message"]["accountKeys"][0] = json!("not-a-pubkey");
+
+        let (engine, _servers) = mock_engine(&[tx.clone()], "finalized", 1).await;
+        let result = engine.verify_transaction(&Signature::new_unique()).await.unwrap();
+
+        assert!(result.verified);
+        assert!(result.parsed_transaction.is_none());
+        assert!(result.audit.unwrap().entries.iter().any(|entry| matches!(
+            entry.step,
+            AuditStep::Parse { error: Some(_), .. }
+        )));
+
+        // Execution status is checked before parsing
+        let error = json!({ "InstructionError": [0, { "Custom": 1 }] });
+        tx["meta"]["err"] = error.clone();
+        tx["meta"]["status"] = json!({ "Err": error });
+
+        let (engine, _servers) = mock_engine(&[tx.clone()], "finalized", 1).await;
+        let result = engine.verify_transaction(&Signature::new_unique()).await.unwrap();
+
+        assert!(matches!(
+            result.outcome,
+            VerificationOutcome::Failed { reason: FailureReason::TransactionFailed { .. }, .. }
+        ));
+
+        // A response without status metadata is retried, not failed for good
+        tx["meta"] = serde_json::Value::Null;
+        let (engine, _servers) = mock_engine(&[tx], "finalized", 1).await;
+        let result = engine.verify_transaction(&Signature::new_unique()).await.unwrap();
+
+        assert!(result.is_retryable());
+        assert!(matches!(
+            result.outcome,
+            VerificationOutcome::Failed { reason: FailureReason::RpcUnavailable { .. }, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reverify_regrades_finalized_result() {
+        let mut server = mock_server().await;
+        mock_rpc(&mut server, "getTransaction", json!(sample_transaction(100, "2", 995_000))).await;
+        server
+            .mock("POST", "/")
+            .match_body(mockito::Matcher::PartialJson(json!({ "method": "getSignatureStatuses" })))
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "jsonrpc": "2.0", "id": 1, "result": signature_status(100, "confirmed") }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        mock_rpc(&mut server, "getSignatureStatuses", signature_status(100, "finalized")).await;
+
+        let cache = Arc::new(ResultCache::new(10, Duration::from_secs(60)));
+        let rpc_client = MultiRpcClient::new(vec![server.url()], 1, Duration::from_secs(2));
+        let engine = VerificationEngine::new(Arc::new(rpc_client), Arc::new(HealthMonitor::default()))
+            .with_reorg_watch(Arc::new(ReorgWatch::new()))
+            .with_cache(Arc::clone(&cache));
+
+        let signature = Signature::new_unique();
+        let confirmed = engine.verify_transaction(&signature).await.unwrap();
+        assert_eq!(confirmed.finality_level, FinalityLevel::Fast);
+
+        engine.reverify(&signature).await.unwrap();
+
+        let cached = engine.verify_transaction(&signature).await.unwrap();
+        assert_eq!(cached.finality_level, FinalityLevel::UltraSafe);
+        assert!(cached.risk_score < confirmed.risk_score);
+        assert!(cached.is_safe(&AcceptancePolicy::standard()));
+        assert_eq!(cache.stats().await.finalized_entries, 1);
+    }
+}
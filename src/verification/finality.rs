@@ -1,73 +1,123 @@
-use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
-use tracing::debug;
-
-use crate::error::Result;
-use crate::types::FinalityLevel;
-
-/// Finality checker - determines if transaction is safely finalized
-pub struct FinalityChecker {
-    commitment: CommitmentConfig,
-}
-
-impl FinalityChecker {
-    pub fn new() -> Self {
-        Self {
-            commitment: CommitmentConfig::finalized(),
-        }
-    }
-
-    /// Check finality level based on confirmations
-    pub async fn check_finality(
-        &self,
-        client: &RpcClient,
-        slot: u64,
-    ) -> Result<FinalityLevel> {
-        // Get current slot
-        let current_slot = client.get_slot()?;
-        let confirmations = current_slot.saturating_sub(slot);
-
-        debug!("Slot {} has {} confirmations", slot, confirmations);
-
-        // Determine finality level based on confirmations
-        let finality = match confirmations {
-            0..=31 => FinalityLevel::Fast,      // <32 confirmations
-            32..=63 => FinalityLevel::Safe,     // 32-63 confirmations  
-            _ => FinalityLevel::UltraSafe,      // 64+ confirmations
-        };
-
-        Ok(finality)
-    }
-
-    /// Check if transaction is in a finalized block
-    pub fn is_finalized(&self, client: &RpcClient, slot: u64) -> Result<bool> {
-        // Query with finalized commitment
-        match client.get_block_with_config(
-            slot,
-            solana_client::rpc_config::RpcBlockConfig {
-                commitment: Some(self.commitment),
-                ..Default::default()
-            },
-        ) {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
-        }
-    }
-}
-
-impl Default for FinalityChecker {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_finality_checker_creation() {
-        let checker = FinalityChecker::new();
-        assert_eq!(checker.commitment, CommitmentConfig::finalized());
-    }
-}
\ No newline at end of file
+use solana_sdk::signature::Signature;
+use tracing::debug;
+
+use crate::error::Result;
+use crate::rpc::{ConsensusOutcome, MultiRpcClient};
+use crate::types::{Commitment, FinalityLevel, SignatureConfirmation};
+
+/// Finality checker - determines if transaction is safely finalized
+pub struct FinalityChecker {
+    commitment: Commitment,
+}
+
+impl FinalityChecker {
+    pub fn new() -> Self {
+        Self {
+            commitment: Commitment::Finalized,
+        }
+    }
+
+    /// Check which commitment the transaction reached across RPCs
+    ///
+    /// Uses `getSignatureStatuses`, requiring the same consensus as
+    /// transaction fetches.
+    pub async fn check_finality(
+        &self,
+        client: &MultiRpcClient,
+        signature: &Signature,
+        slot: u64,
+    ) -> Result<ConsensusOutcome<SignatureConfirmation>> {
+        let outcome = client
+            .get_signature_status_with_consensus(signature, slot)
+            .await?;
+
+        debug!(
+            "Slot {} reached {:?} with {:?} confirmations (dissenting={:?})",
+            slot, outcome.value.commitment, outcome.value.confirmations, outcome.dissenting
+        );
+
+        Ok(outcome)
+    }
+
+    /// Check many transactions at once, sharing one status request per RPC
+    ///
+    /// Results line up with `signatures` (signature, slot) pairs.
+    pub async fn check_finality_batch(
+        &self,
+        client: &MultiRpcClient,
+        signatures: &[(Signature, u64)],
+    ) -> Vec<Result<ConsensusOutcome<SignatureConfirmation>>> {
+        if signatures.is_empty() {
+            return Vec::new();
+        }
+
+        client.get_signature_statuses_with_consensus(signatures).await
+    }
+
+    /// Map commitment (and stake confirmation, if known) to a finality level
+    ///
+    /// Only finalized (rooted) transactions reach UltraSafe. A confirmed
+    /// transaction has a supermajority vote and may reach Safe once enough
+    /// stake has voted past it.
+    pub fn finality_level(
+        &self,
+        confirmation: &SignatureConfirmation,
+        stake_percent: Option<f64>,
+    ) -> FinalityLevel {
+        match confirmation.commitment {
+            Commitment::Finalized => FinalityLevel::UltraSafe,
+            Commitment::Confirmed => stake_percent
+                .map(FinalityLevel::from_stake_percent)
+                .unwrap_or(FinalityLevel::Fast)
+                .min(FinalityLevel::Safe),
+            Commitment::Processed => FinalityLevel::Fast,
+        }
+    }
+
+    /// Check if transaction has reached finalized commitment
+    pub fn is_finalized(&self, confirmation: &SignatureConfirmation) -> bool {
+        confirmation.commitment >= self.commitment
+    }
+}
+
+impl Default for FinalityChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn confirmation(commitment: Commitment) -> SignatureConfirmation {
+        SignatureConfirmation {
+            slot: 1000,
+            commitment,
+            confirmations: None,
+        }
+    }
+
+    #[test]
+    fn test_finality_checker_creation() {
+        let checker = FinalityChecker::new();
+        assert_eq!(checker.commitment, Commitment::Finalized);
+    }
+
+    #[test]
+    fn test_finality_from_commitment() {
+        let checker = FinalityChecker::new();
+
+        let finalized = confirmation(Commitment::Finalized);
+        assert_eq!(checker.finality_level(&finalized, None), FinalityLevel::UltraSafe);
+        assert!(checker.is_finalized(&finalized));
+
+        let confirmed = confirmation(Commitment::Confirmed);
+        assert_eq!(checker.finality_level(&confirmed, None), FinalityLevel::Fast);
+        assert_eq!(checker.finality_level(&confirmed, Some(95.0)), FinalityLevel::Safe);
+        assert!(!checker.is_finalized(&confirmed));
+
+        let processed = confirmation(Commitment::Processed);
+        assert_eq!(checker.finality_level(&processed, Some(95.0)), FinalityLevel::Fast);
+    }
+}
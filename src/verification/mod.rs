@@ -1,7 +1,13 @@
-mod engine;
-mod finality;
-mod risk;
-
-pub use engine::VerificationEngine;
-pub use finality::FinalityChecker;
-pub use risk::RiskScorer;
\ No newline at end of file
+mod audit;
+mod cache;
+mod engine;
+mod finality;
+mod risk;
+mod watch;
+
+pub use audit::AuditLog;
+pub use cache::{CacheStats, ResultCache};
+pub use engine::VerificationEngine;
+pub use finality::FinalityChecker;
+pub use risk::{AmountFactor, ConsensusFactor, FinalityFactor, NetworkHealthFactor, RiskContext, RiskFactor, RiskScorer};
+pub use watch::{ReorgWatch, WatchUpdate};
\ No newline at end of file
@@ -1,86 +1,341 @@
-use crate::types::{FinalityLevel, NetworkHealth};
-
-/// Risk scorer - calculates risk score for verification
-pub struct RiskScorer;
-
-impl RiskScorer {
-    pub fn new() -> Self {
-        Self
-    }
-
-    /// Calculate risk score (0.0 = no risk, 1.0 = maximum risk)
-    pub fn calculate_risk(
-        &self,
-        finality: FinalityLevel,
-        network_health: NetworkHealth,
-        consensus_ratio: f64,
-    ) -> f64 {
-        let mut risk = 0.0;
-
-        // Finality risk
-        risk += match finality {
-            FinalityLevel::UltraSafe => 0.01,
-            FinalityLevel::Safe => 0.05,
-            FinalityLevel::Fast => 0.15,
-        };
-
-        // Network health risk
-        risk += match network_health {
-            NetworkHealth::Healthy => 0.0,
-            NetworkHealth::Forked => 0.3,
-            NetworkHealth::Halted => 0.5,
-        };
-
-        // Consensus risk (lower consensus = higher risk)
-        let consensus_risk = 1.0 - consensus_ratio;
-        risk += consensus_risk * 0.2;
-
-        // Clamp to [0.0, 1.0]
-        risk.clamp(0.0, 1.0)
-    }
-
-    /// Determine if risk is acceptable
-    pub fn is_acceptable_risk(&self, risk_score: f64, threshold: f64) -> bool {
-        risk_score <= threshold
-    }
-}
-
-impl Default for RiskScorer {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_ultra_safe_risk() {
-        let scorer = RiskScorer::new();
-        let risk = scorer.calculate_risk(
-            FinalityLevel::UltraSafe,
-            NetworkHealth::Healthy,
-            1.0, // Perfect consensus
-        );
-        assert!(risk < 0.1); // Very low risk
-    }
-
-    #[test]
-    fn test_forked_network_risk() {
-        let scorer = RiskScorer::new();
-        let risk = scorer.calculate_risk(
-            FinalityLevel::Fast,
-            NetworkHealth::Forked,
-            0.75,
-        );
-        assert!(risk > 0.3); // High risk due to fork
-    }
-
-    #[test]
-    fn test_risk_threshold() {
-        let scorer = RiskScorer::new();
-        assert!(scorer.is_acceptable_risk(0.1, 0.2));
-        assert!(!scorer.is_acceptable_risk(0.3, 0.2));
-    }
-}
\ No newline at end of file
+use std::collections::HashMap;
+
+use crate::config::AmountTier;
+use crate::parsers::ParsedTransaction;
+use crate::types::{FinalityLevel, NetworkHealth, RiskContribution};
+
+/// What a risk factor can see about the verification being scored
+#[derive(Debug, Clone, Copy)]
+pub struct RiskContext<'a> {
+    pub finality: FinalityLevel,
+    pub network_health: NetworkHealth,
+    /// Agreeing endpoints over all configured endpoints
+    pub consensus_ratio: f64,
+    pub parsed_tx: Option<&'a ParsedTransaction>,
+    /// Slots between the transaction and the highest observed slot, when known
+    pub slot_age: Option<u64>,
+    /// Configured tier the transferred amount falls in
+    pub amount_tier: Option<&'a AmountTier>,
+}
+
+impl<'a> RiskContext<'a> {
+    pub fn new(finality: FinalityLevel, network_health: NetworkHealth, consensus_ratio: f64) -> Self {
+        Self {
+            finality,
+            network_health,
+            consensus_ratio,
+            parsed_tx: None,
+            slot_age: None,
+            amount_tier: None,
+        }
+    }
+
+    pub fn with_parsed_transaction(mut self, parsed_tx: Option<&'a ParsedTransaction>) -> Self {
+        self.parsed_tx = parsed_tx;
+        self
+    }
+
+    pub fn with_slot_age(mut self, slot_age: Option<u64>) -> Self {
+        self.slot_age = slot_age;
+        self
+    }
+
+    pub fn with_amount_tier(mut self, amount_tier: Option<&'a AmountTier>) -> Self {
+        self.amount_tier = amount_tier;
+        self
+    }
+
+    /// Transferred amount of the bridge instruction, if it carries one
+    pub fn amount(&self) -> Option<u64> {
+        self.parsed_tx.and_then(|tx| tx.amount())
+    }
+}
+
+/// One input to the risk score
+///
+/// `score` returns a raw risk in [0.0, 1.0]; the scorer multiplies it by the
+/// factor's weight, which defaults to `default_weight` unless configured.
+pub trait RiskFactor: Send + Sync {
+    /// Label used in the breakdown and as the key in configured weights
+    fn label(&self) -> &str;
+
+    fn default_weight(&self) -> f64;
+
+    fn score(&self, context: &RiskContext<'_>) -> f64;
+}
+
+/// Weaker finality means a larger rollback window
+pub struct FinalityFactor;
+
+impl RiskFactor for FinalityFactor {
+    fn label(&self) -> &str {
+        "finality"
+    }
+
+    fn default_weight(&self) -> f64 {
+        0.15
+    }
+
+    fn score(&self, context: &RiskContext<'_>) -> f64 {
+        match context.finality {
+            FinalityLevel::UltraSafe => 1.0 / 15.0,
+            FinalityLevel::Safe => 1.0 / 3.0,
+            FinalityLevel::Fast => 1.0,
+        }
+    }
+}
+
+pub struct NetworkHealthFactor;
+
+impl RiskFactor for NetworkHealthFactor {
+    fn label(&self) -> &str {
+        "network_health"
+    }
+
+    fn default_weight(&self) -> f64 {
+        0.5
+    }
+
+    fn score(&self, context: &RiskContext<'_>) -> f64 {
+        match context.network_health {
+            NetworkHealth::Healthy => 0.0,
+            NetworkHealth::Degraded => 0.2,
+            NetworkHealth::Slow => 0.4,
+            NetworkHealth::Forked => 0.6,
+            NetworkHealth::Halted => 1.0,
+        }
+    }
+}
+
+/// Lower consensus = higher risk
+pub struct ConsensusFactor;
+
+impl RiskFactor for ConsensusFactor {
+    fn label(&self) -> &str {
+        "consensus"
+    }
+
+    fn default_weight(&self) -> f64 {
+        0.2
+    }
+
+    fn score(&self, context: &RiskContext<'_>) -> f64 {
+        1.0 - context.consensus_ratio
+    }
+}
+
+/// Larger transfers are worth more to an attacker; contributes the amount tier's risk weight
+pub struct AmountFactor;
+
+impl RiskFactor for AmountFactor {
+    fn label(&self) -> &str {
+        "amount"
+    }
+
+    fn default_weight(&self) -> f64 {
+        1.0
+    }
+
+    fn score(&self, context: &RiskContext<'_>) -> f64 {
+        context.amount_tier.map_or(0.0, |tier| tier.risk_weight)
+    }
+}
+
+/// Risk scorer - calculates risk score for verification
+pub struct RiskScorer {
+    factors: Vec<Box<dyn RiskFactor>>,
+    /// Weight per factor label; factors not listed use their default weight
+    weights: HashMap<String, f64>,
+}
+
+impl RiskScorer {
+    /// Scorer with the finality, network health, consensus and amount factors
+    pub fn new() -> Self {
+        Self {
+            factors: vec![
+                Box::new(FinalityFactor),
+                Box::new(NetworkHealthFactor),
+                Box::new(ConsensusFactor),
+                Box::new(AmountFactor),
+            ],
+            weights: HashMap::new(),
+        }
+    }
+
+    /// Override factor weights by label
+    pub fn with_weights(mut self, weights: HashMap<String, f64>) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    pub fn with_factor(mut self, factor: Box<dyn RiskFactor>) -> Self {
+        self.factors.push(factor);
+        self
+    }
+
+    fn weight(&self, factor: &dyn RiskFactor) -> f64 {
+        self.weights
+            .get(factor.label())
+            .copied()
+            .unwrap_or_else(|| factor.default_weight())
+    }
+
+    /// Calculate risk score (0.0 = no risk, 1.0 = maximum risk)
+    pub fn calculate_risk(
+        &self,
+        finality: FinalityLevel,
+        network_health: NetworkHealth,
+        consensus_ratio: f64,
+    ) -> f64 {
+        self.score(&RiskContext::new(finality, network_health, consensus_ratio))
+    }
+
+    /// Sum of all factor contributions, clamped to [0.0, 1.0]
+    pub fn score(&self, context: &RiskContext<'_>) -> f64 {
+        Self::total(&self.breakdown(context))
+    }
+
+    /// Sum of a breakdown's contributions, clamped to [0.0, 1.0]
+    pub fn total(breakdown: &[RiskContribution]) -> f64 {
+        let risk: f64 = breakdown.iter().map(|component| component.contribution).sum();
+        risk.clamp(0.0, 1.0)
+    }
+
+    /// Weighted contribution of each factor, before clamping
+    pub fn breakdown(&self, context: &RiskContext<'_>) -> Vec<RiskContribution> {
+        self.factors
+            .iter()
+            .map(|factor| {
+                let weight = self.weight(factor.as_ref());
+                RiskContribution {
+                    component: factor.label().to_string(),
+                    weight,
+                    contribution: weight * factor.score(context).clamp(0.0, 1.0),
+                }
+            })
+            .collect()
+    }
+
+    /// Determine if risk is acceptable
+    pub fn is_acceptable_risk(&self, risk_score: f64, threshold: f64) -> bool {
+        risk_score <= threshold
+    }
+}
+
+impl Default for RiskScorer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ultra_safe_risk() {
+        let scorer = RiskScorer::new();
+        let risk = scorer.calculate_risk(
+            FinalityLevel::UltraSafe,
+            NetworkHealth::Healthy,
+            1.0, // Perfect consensus
+        );
+        assert!(risk < 0.1); // Very low risk
+    }
+
+    #[test]
+    fn test_forked_network_risk() {
+        let scorer = RiskScorer::new();
+        let risk = scorer.calculate_risk(
+            FinalityLevel::Fast,
+            NetworkHealth::Forked,
+            0.75,
+        );
+        assert!(risk > 0.3); // High risk due to fork
+    }
+
+    #[test]
+    fn test_degraded_network_risk() {
+        let scorer = RiskScorer::new();
+        let healthy = scorer.calculate_risk(FinalityLevel::Safe, NetworkHealth::Healthy, 1.0);
+        let degraded = scorer.calculate_risk(FinalityLevel::Safe, NetworkHealth::Degraded, 1.0);
+        let forked = scorer.calculate_risk(FinalityLevel::Safe, NetworkHealth::Forked, 1.0);
+
+        assert!(healthy < degraded && degraded < forked);
+    }
+
+    #[test]
+    fn test_risk_threshold() {
+        let scorer = RiskScorer::new();
+        assert!(scorer.is_acceptable_risk(0.1, 0.2));
+        assert!(!scorer.is_acceptable_risk(0.3, 0.2));
+    }
+
+    #[test]
+    fn test_breakdown_uses_configured_weights() {
+        let context = RiskContext::new(FinalityLevel::Fast, NetworkHealth::Degraded, 0.5);
+
+        let default = RiskScorer::new().breakdown(&context);
+        let labels: Vec<&str> = default.iter().map(|c| c.component.as_str()).collect();
+        assert_eq!(labels, ["finality", "network_health", "consensus", "amount"]);
+        assert!((default[0].contribution - 0.15).abs() < 1e-9);
+        assert!((default[1].contribution - 0.1).abs() < 1e-9);
+        assert!((default[2].contribution - 0.1).abs() < 1e-9);
+        assert_eq!(default[3].contribution, 0.0);
+
+        let weights = HashMap::from([("consensus".to_string(), 0.0)]);
+        let scorer = RiskScorer::new().with_weights(weights);
+        let breakdown = scorer.breakdown(&context);
+        assert_eq!(breakdown[2].contribution, 0.0);
+        assert!((scorer.score(&context) - 0.25).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_custom_factor() {
+        struct LargeAmount;
+
+        impl RiskFactor for LargeAmount {
+            fn label(&self) -> &str {
+                "large_amount"
+            }
+
+            fn default_weight(&self) -> f64 {
+                0.3
+            }
+
+            fn score(&self, context: &RiskContext<'_>) -> f64 {
+                if context.amount().unwrap_or(0) > 1_000_000 { 1.0 } else { 0.0 }
+            }
+        }
+
+        let scorer = RiskScorer::new().with_factor(Box::new(LargeAmount));
+        let parsed = ParsedTransaction::new(
+            crate::parsers::BridgeType::Wormhole,
+            crate::parsers::BridgeInstruction::TransferNative {
+                amount: 5_000_000,
+                target_chain: 2,
+                recipient: vec![],
+            },
+        );
+
+        let context = RiskContext::new(FinalityLevel::UltraSafe, NetworkHealth::Healthy, 1.0);
+        let small = scorer.score(&context);
+        let large = scorer.score(&context.with_parsed_transaction(Some(&parsed)));
+        assert!((large - small - 0.3).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_amount_tier_raises_risk() {
+        let tier = AmountTier {
+            min_amount: 1_000_000,
+            required_finality: FinalityLevel::UltraSafe,
+            risk_weight: 0.4,
+        };
+        let scorer = RiskScorer::new();
+        let context = RiskContext::new(FinalityLevel::Safe, NetworkHealth::Healthy, 1.0);
+
+        let base = scorer.score(&context);
+        let tiered = scorer.score(&context.with_amount_tier(Some(&tier)));
+        assert!((tiered - base - 0.4).abs() < 1e-9);
+    }
+}
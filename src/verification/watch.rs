@@ -0,0 +1,244 @@
+use chrono::{Duration, Utc};
+use solana_sdk::signature::Signature;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use tracing::{debug, info, warn};
+
+use crate::rpc::{MultiRpcClient, SignatureRetention};
+use crate::types::{Commitment, VerificationResult};
+
+// Revoked results stay retrievable this long
+const REVOKED_RETENTION_SECS: i64 = 3600;
+// Finalization takes seconds; anything older is abandoned (e.g. RPCs unreachable)
+const MAX_WATCH_SECS: i64 = 600;
+
+/// Change in a watched verification found by a re-check
+#[derive(Debug, Clone)]
+pub enum WatchUpdate {
+    /// The transaction reached finalized commitment and is no longer watched
+    ///
+    /// Its earlier verdict (finality, risk, stake) is stale and should be re-verified.
+    Finalized(Signature),
+    /// A quorum now places the transaction in another slot, e.g. re-included
+    /// on the surviving fork; it should be re-verified there
+    Moved { signature: Signature, slot: u64 },
+    /// A quorum no longer knows the transaction
+    Revoked(Box<VerificationResult>),
+}
+
+/// Watch list of verified transactions that are not finalized yet
+///
+/// A verification issued before finalization can be undone by a rollback.
+/// Watched signatures are re-checked until finalized; ones a quorum no longer
+/// knows are revoked, and ones found in another slot are handed back for
+/// re-verification.
+pub struct ReorgWatch {
+    watched: RwLock<HashMap<Signature, VerificationResult>>,
+    revoked: RwLock<HashMap<Signature, VerificationResult>>,
+}
+
+impl ReorgWatch {
+    pub fn new() -> Self {
+        Self {
+            watched: RwLock::new(HashMap::new()),
+            revoked: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Watch a verified result until it is finalized (finalized ones are ignored)
+    pub async fn watch(&self, result: VerificationResult) {
+        if !result.verified || result.commitment == Some(Commitment::Finalized) {
+            return;
+        }
+
+        debug!("Watching {} at slot {} until finalized", result.signature, result.slot);
+        self.watched.write().await.insert(result.signature, result);
+    }
+
+    /// Revoked result for a signature, while it is retained
+    pub async fn revoked(&self, signature: &Signature) -> Option<VerificationResult> {
+        self.revoked.read().await.get(signature).cloned()
+    }
+
+    pub async fn watched_count(&self) -> usize {
+        self.watched.read().await.len()
+    }
+
+    /// Re-check every watched signature once
+    ///
+    /// Inconclusive checks (RPC errors, no quorum either way) leave the
+    /// entry watched until it ages out.
+    pub async fn recheck(&self, rpc_client: &MultiRpcClient) -> Vec<WatchUpdate> {
+        let pending: Vec<(Signature, u64)> = self
+            .watched
+            .read()
+            .await
+            .values()
+            .map(|result| (result.signature, result.slot))
+            .collect();
+
+        let mut updates = Vec::new();
+        for (signature, slot) in pending {
+            match rpc_client.check_signature_retained(&signature, slot).await {
+                Ok(SignatureRetention::Retained(outcome))
+                    if outcome.value.commitment == Commitment::Finalized =>
+                {
+                    if self.watched.write().await.remove(&signature).is_some() {
+                        info!("{} finalized at slot {}", signature, slot);
+                        updates.push(WatchUpdate::Finalized(signature));
+                    }
+                }
+                Ok(SignatureRetention::Retained(outcome)) => {
+                    if let Some(result) = self.watched.write().await.get_mut(&signature) {
+                        result.commitment = Some(outcome.value.commitment);
+                    }
+                }
+                Ok(SignatureRetention::Moved(new_slot)) => {
+                    if self.watched.write().await.remove(&signature).is_some() {
+                        info!("{} moved from slot {} to {}", signature, slot, new_slot);
+                        updates.push(WatchUpdate::Moved { signature, slot: new_slot });
+                    }
+                }
+                Ok(SignatureRetention::Vanished) => {
+                    if let Some(result) = self.watched.write().await.remove(&signature) {
+                        warn!("Revoking verification of {} (slot {})", signature, slot);
+                        let revoked = result.revoke(format!(
+                            "Transaction from slot {} no longer found by RPC quorum",
+                            slot
+                        ));
+                        self.revoked.write().await.insert(signature, revoked.clone());
+                        updates.push(WatchUpdate::Revoked(Box::new(revoked)));
+                    }
+                }
+                Err(e) => debug!("Reorg check for {} inconclusive: {}", signature, e),
+            }
+        }
+
+        self.prune().await;
+        updates
+    }
+
+    async fn prune(&self) {
+        let now = Utc::now();
+
+        self.watched.write().await.retain(|signature, result| {
+            let keep = now - result.timestamp < Duration::seconds(MAX_WATCH_SECS);
+            if !keep {
+                warn!("{} not finalized after {}s, no longer watched", signature, MAX_WATCH_SECS);
+            }
+            keep
+        });
+
+        self.revoked.write().await.retain(|_, result| {
+            result
+                .revocation
+                .as_ref()
+                .is_some_and(|r| now - r.revoked_at < Duration::seconds(REVOKED_RETENTION_SECS))
+        });
+    }
+}
+
+impl Default for ReorgWatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{mock_rpc, mock_server, signature_status};
+    use std::str::FromStr;
+
+    fn verified(commitment: Commitment) -> VerificationResult {
+        let sig = Signature::from_str(
+            "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW"
+        ).unwrap();
+
+        VerificationResult::new(sig, 1000)
+            .with_verification(true)
+            .with_commitment(commitment)
+    }
+
+    /// Client over one mock endpoint answering signature status lookups with `statuses`
+    async fn status_client(statuses: serde_json::Value) -> (MultiRpcClient, mockito::ServerGuard) {
+        let mut server = mock_server().await;
+        mock_rpc(&mut server, "getSignatureStatuses", statuses).await;
+        let client = MultiRpcClient::new(vec![server.url()], 1, std::time::Duration::from_secs(2));
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn test_only_unfinalized_results_are_watched() {
+        let watch = ReorgWatch::new();
+
+        watch.watch(verified(Commitment::Finalized)).await;
+        assert_eq!(watch.watched_count().await, 0);
+
+        let confirmed = verified(Commitment::Confirmed);
+        watch.watch(confirmed.clone()).await;
+        assert_eq!(watch.watched_count().await, 1);
+        assert!(watch.revoked(&confirmed.signature).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recheck_reports_finalized() {
+        let watch = ReorgWatch::new();
+        let confirmed = verified(Commitment::Confirmed);
+        watch.watch(confirmed.clone()).await;
+
+        // Still confirmed: stays watched, no update
+        let (client, _server) = status_client(signature_status(1000, "confirmed")).await;
+        assert!(watch.recheck(&client).await.is_empty());
+        assert_eq!(watch.watched_count().await, 1);
+
+        let (client, _server) = status_client(signature_status(1000, "finalized")).await;
+        let updates = watch.recheck(&client).await;
+
+        assert!(matches!(
+            updates.as_slice(),
+            [WatchUpdate::Finalized(signature)] if *signature == confirmed.signature
+        ));
+        assert_eq!(watch.watched_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_recheck_revokes_vanished_transaction() {
+        let watch = ReorgWatch::new();
+        let confirmed = verified(Commitment::Confirmed);
+        watch.watch(confirmed.clone()).await;
+
+        let vanished = serde_json::json!({ "context": { "slot": 1040 }, "value": [null] });
+        let (client, _server) = status_client(vanished).await;
+        let updates = watch.recheck(&client).await;
+
+        let [WatchUpdate::Revoked(revoked)] = updates.as_slice() else {
+            panic!("expected one revocation, got {:?}", updates);
+        };
+        assert!(!revoked.verified);
+        assert!(revoked.revocation.is_some());
+        assert_eq!(watch.watched_count().await, 0);
+        assert_eq!(
+            watch.revoked(&confirmed.signature).await.map(|result| result.verified),
+            Some(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recheck_reports_slot_move_without_revoking() {
+        let watch = ReorgWatch::new();
+        let confirmed = verified(Commitment::Confirmed);
+        watch.watch(confirmed.clone()).await;
+
+        // Re-included on the surviving fork a few slots later
+        let (client, _server) = status_client(signature_status(1003, "confirmed")).await;
+        let updates = watch.recheck(&client).await;
+
+        assert!(matches!(
+            updates.as_slice(),
+            [WatchUpdate::Moved { signature, slot: 1003 }] if *signature == confirmed.signature
+        ));
+        assert_eq!(watch.watched_count().await, 0);
+        assert!(watch.revoked(&confirmed.signature).await.is_none());
+    }
+}